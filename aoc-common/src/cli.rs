@@ -0,0 +1,209 @@
+use std::env;
+use std::process;
+use std::str::FromStr;
+
+// How a binary prints its answers.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+// The command line shared by every day's binary:
+// `cargo run <input>... [--format text|json]`, where each input is a file,
+// a directory of .txt files, or `-` for standard input. Binaries can take
+// extra flags of their own, given as "--name" or "--name VALUE".
+#[derive(Debug, PartialEq, Eq)]
+pub struct Args {
+    pub inputs: Vec<String>,
+    pub format: Format,
+    pub flags: Vec<(String, Option<String>)>,
+    usage: String,
+}
+
+impl Args {
+    // Reads the arguments the binary was run with, exiting with a usage
+    // message if they don't make sense.
+    pub fn from_env() -> Self {
+        Self::from_env_with(&[])
+    }
+
+    // Like `from_env`, for a binary that also takes the given extra flags.
+    pub fn from_env_with(extra: &[&str]) -> Self {
+        Self::parse_with(env::args().skip(1), extra).unwrap_or_else(|message| {
+            eprintln!("{message}");
+            eprintln!("{}", usage(extra));
+            process::exit(2);
+        })
+    }
+
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        Self::parse_with(args, &[])
+    }
+
+    pub fn parse_with(
+        args: impl IntoIterator<Item = String>,
+        extra: &[&str],
+    ) -> Result<Self, String> {
+        let mut inputs = vec![];
+        let mut format = Format::Text;
+        let mut flags = vec![];
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" => {
+                    format = match args.next().as_deref() {
+                        Some("text") => Format::Text,
+                        Some("json") => Format::Json,
+                        Some(other) => return Err(format!("unknown format {other:?}")),
+                        None => return Err("--format needs a value".to_string()),
+                    }
+                }
+                _ if arg.starts_with("--") => {
+                    let flag = extra
+                        .iter()
+                        .find(|flag| flag.split(' ').next() == Some(arg.as_str()))
+                        .ok_or_else(|| format!("unexpected argument {arg:?}"))?;
+                    let value = if flag.contains(' ') {
+                        let value = args.next().ok_or_else(|| format!("{arg} needs a value"))?;
+                        Some(value)
+                    } else {
+                        None
+                    };
+                    flags.push((arg, value));
+                }
+                _ => inputs.push(arg),
+            }
+        }
+
+        if inputs.is_empty() {
+            return Err("please supply an input filename".to_string());
+        }
+        Ok(Self {
+            inputs,
+            format,
+            flags,
+            usage: usage(extra),
+        })
+    }
+
+    // whether the flag was given
+    pub fn has(&self, flag: &str) -> bool {
+        self.flags.iter().any(|(name, _)| name == flag)
+    }
+
+    // The value given for the flag (the last one, if it was given more than
+    // once), exiting with the usage message if it isn't a valid `T`.
+    pub fn value<T: FromStr>(&self, flag: &str) -> Option<T> {
+        let (_, value) = self.flags.iter().rev().find(|(name, _)| name == flag)?;
+        let value = value.as_deref()?;
+        match value.parse() {
+            Ok(value) => Some(value),
+            Err(_) => {
+                eprintln!("invalid value for {flag}: {value:?}");
+                eprintln!("{}", self.usage);
+                process::exit(2);
+            }
+        }
+    }
+
+    // An error if more than one of the flags was given, for modes that can't
+    // be combined.
+    pub fn at_most_one_of(&self, flags: &[&str]) -> Result<(), String> {
+        let given: Vec<&str> = flags
+            .iter()
+            .copied()
+            .filter(|flag| self.has(flag))
+            .collect();
+        match given.as_slice() {
+            [first, second, ..] => Err(format!("{first} and {second} can't be used together")),
+            _ => Ok(()),
+        }
+    }
+
+    // An error if the flag was given without any of the modes it applies to.
+    pub fn only_with(&self, flag: &str, modes: &[&str]) -> Result<(), String> {
+        if !self.has(flag) || modes.iter().any(|mode| self.has(mode)) {
+            return Ok(());
+        }
+        Err(format!("{flag} only applies with {}", modes.join(" or ")))
+    }
+}
+
+fn usage(extra: &[&str]) -> String {
+    let mut usage = String::from("usage: <input-file|directory|->... [--format text|json]");
+    for flag in extra {
+        usage += &format!(" [{flag}]");
+    }
+    usage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parse_args() {
+        let args = parse(&["input.txt"]).unwrap();
+        assert_eq!(args.inputs, ["input.txt"]);
+        assert_eq!(args.format, Format::Text);
+
+        let args = parse(&["--format", "json", "input.txt"]).unwrap();
+        assert_eq!(args.format, Format::Json);
+
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["input.txt", "--format", "yaml"]).is_err());
+        assert!(parse(&["input.txt", "--verbose"]).is_err());
+
+        let args = parse(&["input.txt", "-", "inputs", "--format", "text"]).unwrap();
+        assert_eq!(args.inputs, ["input.txt", "-", "inputs"]);
+    }
+
+    #[test]
+    fn parse_extra_flags() {
+        let extra = ["--top K", "--stats"];
+        let parse =
+            |args: &[&str]| Args::parse_with(args.iter().map(|arg| arg.to_string()), &extra);
+
+        let args = parse(&["--top", "5", "input.txt", "--stats"]).unwrap();
+        assert_eq!(args.inputs, ["input.txt"]);
+        assert_eq!(args.value::<usize>("--top"), Some(5));
+        assert!(args.has("--stats"));
+
+        let args = parse(&["input.txt"]).unwrap();
+        assert_eq!(args.value::<usize>("--top"), None);
+        assert!(!args.has("--stats"));
+
+        assert!(parse(&["input.txt", "--top"]).is_err());
+        assert!(parse(&["input.txt", "--verbose"]).is_err());
+    }
+
+    #[test]
+    fn check_flags() {
+        let extra = ["--top K", "--stats", "--seed N"];
+        let parse = |args: &[&str]| {
+            Args::parse_with(args.iter().map(|arg| arg.to_string()), &extra).unwrap()
+        };
+
+        let args = parse(&["input.txt", "--top", "3", "--stats"]);
+        assert_eq!(
+            args.at_most_one_of(&["--top", "--stats"]),
+            Err(String::from("--top and --stats can't be used together"))
+        );
+        assert_eq!(args.at_most_one_of(&["--top", "--seed"]), Ok(()));
+
+        let args = parse(&["input.txt", "--seed", "1"]);
+        assert_eq!(
+            args.only_with("--seed", &["--top", "--stats"]),
+            Err(String::from("--seed only applies with --top or --stats"))
+        );
+        assert_eq!(args.only_with("--top", &["--stats"]), Ok(()));
+        let args = parse(&["input.txt", "--seed", "1", "--stats"]);
+        assert_eq!(args.only_with("--seed", &["--top", "--stats"]), Ok(()));
+    }
+}
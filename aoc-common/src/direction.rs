@@ -0,0 +1,60 @@
+use crate::Point;
+
+// One of the four ways to step across a grid. Up is towards smaller y, so
+// that it points at the previous row of a puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+use Direction::{Down, Left, Right, Up};
+
+impl Direction {
+    // clockwise, starting from up
+    pub const ALL: [Self; 4] = [Up, Right, Down, Left];
+
+    pub fn offset(self) -> Point {
+        match self {
+            Up => Point::new(0, -1),
+            Right => Point::new(1, 0),
+            Down => Point::new(0, 1),
+            Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning() {
+        assert_eq!(Up.turn_right(), Right);
+        assert_eq!(Up.turn_left(), Left);
+        assert_eq!(Left.turn_right(), Up);
+        assert_eq!(Right.opposite(), Left);
+
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction.offset() + direction.opposite().offset(),
+                Point::ORIGIN
+            );
+            assert_eq!(direction.turn_left().turn_right(), direction);
+        }
+    }
+}
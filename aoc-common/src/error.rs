@@ -0,0 +1,157 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+// A problem found while parsing a puzzle input, pointing at the offending text.
+// Lines and columns are 1-based, and the column counts characters, not bytes.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    pub day: usize,
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub source_line: String,
+    pub message: String,
+    offset: usize,
+}
+
+impl ParseError {
+    // An error about `at`, which must be a slice of `source` (as produced by
+    // `split`, `trim`, `lines` and friends), so that we can work out where in
+    // the source it came from. Use an empty slice to point between characters,
+    // e.g. `&source[source.len()..]` for something missing at the end.
+    pub fn new(day: usize, source: &str, at: &str, message: impl Into<String>) -> Self {
+        Self::at_offset(day, source, offset_within(source, at), at, message.into())
+    }
+
+    // Re-anchors an error that was reported against `fragment` (which must be
+    // a slice of `source`) so that its location is relative to `source`.
+    // This lets parsers for a single line or block be used on their own while
+    // still producing locations within the whole input.
+    pub fn within(self, source: &str, fragment: &str) -> Self {
+        let offset = offset_within(source, fragment) + self.offset;
+        let at = &source[offset..offset + self.snippet.len()];
+        Self::at_offset(self.day, source, offset, at, self.message)
+    }
+
+    fn at_offset(day: usize, source: &str, offset: usize, at: &str, message: String) -> Self {
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let source_line = source[line_start..].split('\n').next().unwrap_or("");
+        let snippet = at.split('\n').next().unwrap_or("");
+
+        Self {
+            day,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            snippet: snippet.to_string(),
+            source_line: source_line.trim_end_matches('\r').to_string(),
+            message,
+            offset,
+        }
+    }
+}
+
+fn offset_within(source: &str, fragment: &str) -> usize {
+    let offset = (fragment.as_ptr() as usize).wrapping_sub(source.as_ptr() as usize);
+    assert!(
+        offset + fragment.len() <= source.len(),
+        "the fragment of an error must be a slice of its source"
+    );
+    offset
+}
+
+// Renders as a caret diagnostic:
+//
+// day 5: invalid integer
+//   --> line 3, column 6
+//    |
+//  3 | move x from 1 to 3
+//    |      ^
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        let carets = "^".repeat(self.snippet.chars().count().max(1));
+        let indent = " ".repeat(self.column - 1);
+
+        writeln!(f, "day {}: {}", self.day, self.message)?;
+        writeln!(f, "{gutter}--> line {}, column {}", self.line, self.column)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{line_number} | {}", self.source_line)?;
+        write!(f, "{gutter} | {indent}{carets}")
+    }
+}
+
+impl Error for ParseError {}
+
+// Parses `at` (a slice of `source`) as a number, pointing at it if it isn't one.
+pub fn parse_number<T: FromStr>(day: usize, source: &str, at: &str) -> Result<T, ParseError> {
+    at.parse()
+        .map_err(|_| ParseError::new(day, source, at, format!("invalid number {at:?}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_the_offending_text() {
+        let source = "move 1 from 2 to 1\nmove x from 1 to 3\n";
+        let at = &source[24..25];
+        let error = ParseError::new(5, source, at, "invalid number");
+
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 6);
+        assert_eq!(error.snippet, "x");
+        assert_eq!(error.source_line, "move x from 1 to 3");
+    }
+
+    #[test]
+    fn points_past_the_end() {
+        let source = "1000\n2000";
+        let error = ParseError::new(1, source, &source[source.len()..], "missing elf");
+
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.snippet, "");
+    }
+
+    #[test]
+    fn reanchors_errors_within_a_larger_source() {
+        let source = "    [D]\n[N] [C]\n 1   2\n\nmove 1 from 2 to 1\nmove x from 1 to 3";
+        let line = source.lines().last().unwrap();
+        let error = ParseError::new(5, line, &line[5..6], "invalid number").within(source, line);
+
+        assert_eq!((error.line, error.column), (6, 6));
+        assert_eq!(error.source_line, "move x from 1 to 3");
+    }
+
+    #[test]
+    fn renders_a_caret_diagnostic() {
+        let source = "Sensor at x=2, y=18\nSensor at x=9, y=abc";
+        let at = &source[source.len() - 3..];
+        let error = ParseError::new(15, source, at, "invalid number");
+
+        assert_eq!(
+            error.to_string(),
+            "day 15: invalid number\n \
+             --> line 2, column 18\n  \
+             |\n\
+             2 | Sensor at x=9, y=abc\n  \
+             |                  ^^^"
+        );
+    }
+
+    #[test]
+    fn parses_numbers() {
+        let source = "12,-4,z";
+        assert_eq!(parse_number::<usize>(18, source, &source[0..2]), Ok(12));
+        assert_eq!(parse_number::<isize>(18, source, &source[3..5]), Ok(-4));
+
+        let error = parse_number::<isize>(18, source, &source[6..]).unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (7, "invalid number \"z\"")
+        );
+    }
+}
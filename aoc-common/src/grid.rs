@@ -0,0 +1,247 @@
+use crate::{Direction, ParseError, Point, Result};
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+// A dense, rectangular grid of cells, stored row by row and indexed by Point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    // cells are given row by row, starting from the top left
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "expected {width}x{height} cells"
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, cell: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![cell; width * height])
+    }
+
+    // Parses a block of text with one cell per character, using `cell` to
+    // turn each character into a cell. Characters that it doesn't accept
+    // are reported as not being what was `expected`, and so are rows that
+    // aren't as wide as the first.
+    pub fn parse(
+        day: usize,
+        input: &str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Self> {
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            let message = format!("expected a grid of {expected}");
+            return Err(ParseError::new(day, input, trimmed, message));
+        }
+
+        let lines: Vec<_> = trimmed.split('\n').collect();
+        let width = lines[0].chars().count();
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for line in &lines {
+            let row_start = cells.len();
+            for (i, c) in line.char_indices() {
+                match cell(c) {
+                    Some(cell) => cells.push(cell),
+                    None => {
+                        let at = &line[i..i + c.len_utf8()];
+                        let message = format!("expected {expected}, got {c:?}");
+                        return Err(ParseError::new(day, input, at, message));
+                    }
+                }
+            }
+            if cells.len() - row_start != width {
+                let message = format!("expected every row to be {width} wide");
+                return Err(ParseError::new(day, input, line, message));
+            }
+        }
+
+        Ok(Self::new(width, lines.len(), cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (0..self.width as isize).contains(&point.x) && (0..self.height as isize).contains(&point.y)
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(|i| &mut self.cells[i])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside the grid");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    // every point in the grid, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Point::new((i % width) as isize, (i / width) as isize))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    // the points of the cells that match, row by row
+    pub fn positions<'a>(
+        &'a self,
+        mut matches: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| matches(cell))
+            .map(|(point, _)| point)
+    }
+
+    // the points in the grid sharing an edge with the point
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point
+            .neighbours()
+            .into_iter()
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    // the points in the grid sharing an edge or a corner with the point
+    pub fn neighbours_8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point
+            .neighbours_8()
+            .into_iter()
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    // the cells met walking from the point (not included) to the edge
+    pub fn ray(&self, from: Point, direction: Direction) -> impl Iterator<Item = (Point, &T)> {
+        let mut point = from;
+        std::iter::from_fn(move || {
+            point = point + direction;
+            Some((point, self.get(point)?))
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.get(point) {
+            Some(cell) => cell,
+            None => panic!(
+                "{point:?} is outside the {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(point) {
+            Some(cell) => cell,
+            None => panic!("{point:?} is outside the {width}x{height} grid"),
+        }
+    }
+}
+
+// one line per row, without a trailing newline
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>> {
+        Grid::parse(1, input, "a digit", |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 6);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = digits("123\n4x6").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.message, "expected a digit, got 'x'");
+
+        let error = digits("123\n45\n789").unwrap_err();
+        assert_eq!((error.line, error.snippet.as_str()), (2, "45"));
+
+        let error = digits("123\n4567").unwrap_err();
+        assert_eq!((error.line, error.snippet.as_str()), (2, "4567"));
+
+        assert!(digits("\n").is_err());
+    }
+
+    #[test]
+    fn rows_columns_and_neighbours() {
+        let grid = digits("123\n456\n789").unwrap();
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.column(2).collect::<Vec<_>>(), [&3, &6, &9]);
+        assert_eq!(grid.positions(|&n| n % 2 == 0).count(), 4);
+
+        let corner: Vec<_> = grid.neighbours(Point::ORIGIN).collect();
+        assert_eq!(corner, [Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.neighbours(Point::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours_8(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours_8(Point::new(2, 2)).count(), 3);
+
+        let ray: Vec<_> = grid.ray(Point::new(0, 2), Direction::Up).collect();
+        assert_eq!(ray, [(Point::new(0, 1), &4), (Point::new(0, 0), &1)]);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{Args, Format, ParseError, Report, Solution};
+use serde_json::json;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process;
+
+// Where a puzzle input comes from.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    // The inputs a command-line argument stands for: `-` is standard input,
+    // a directory is every .txt file directly inside it (in name order), and
+    // anything else is a single file.
+    pub fn expand(arg: &Path) -> io::Result<Vec<Self>> {
+        if arg == Path::new("-") {
+            return Ok(vec![Self::Stdin]);
+        }
+        if !arg.is_dir() {
+            return Ok(vec![Self::File(arg.to_path_buf())]);
+        }
+
+        let mut paths = vec![];
+        for entry in fs::read_dir(arg)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|extension| extension == "txt") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        Ok(paths.into_iter().map(Self::File).collect())
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Self::Stdin => io::read_to_string(io::stdin()),
+            Self::File(path) => fs::read_to_string(path),
+        }
+    }
+
+    // for reading inputs too big to hold in memory
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Self::Stdin => Ok(Box::new(io::stdin().lock())),
+            Self::File(path) => Ok(Box::new(BufReader::new(fs::File::open(path)?))),
+        }
+    }
+}
+
+impl Args {
+    // Every input named on the command line, exiting if a directory can't
+    // be read.
+    pub fn sources(&self) -> Vec<Source> {
+        let mut sources = vec![];
+        for arg in &self.inputs {
+            match Source::expand(Path::new(arg)) {
+                Ok(expanded) => sources.extend(expanded),
+                Err(error) => {
+                    eprintln!("failed to read the directory {arg}: {error}");
+                    process::exit(1);
+                }
+            }
+        }
+        sources
+    }
+
+    // Whether there's more than the single input file the binaries used to
+    // take, in which case the output for each input is labelled.
+    pub fn several_inputs(&self) -> bool {
+        self.inputs.len() != 1 || Path::new(&self.inputs[0]).is_dir()
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Stdin => write!(f, "<stdin>"),
+            Self::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+// How solving one input went, for the summary at the end.
+enum Outcome {
+    Solved(f64),
+    Unreadable(io::Error),
+    Malformed(ParseError),
+}
+
+// Solves every input named on the command line with one of the binaries,
+// printing each report with `print_text` (or as a line of JSON). With more
+// than one input, each report gets a header and a summary follows. Exits
+// with 1 if any input couldn't be read or parsed.
+pub fn solve_inputs<S: Solution>(args: &Args, print_text: impl Fn(&Report<S>)) {
+    let sources = args.sources();
+    let several = args.several_inputs();
+    let mut outcomes = vec![];
+
+    for (i, source) in sources.iter().enumerate() {
+        if several && args.format == Format::Text {
+            let gap = if i > 0 { "\n" } else { "" };
+            println!("{gap}==> {source} <==");
+        }
+
+        let outcome = match source.read() {
+            Err(error) => {
+                eprintln!("failed to read {source}: {error}");
+                Outcome::Unreadable(error)
+            }
+            Ok(input) => match Report::<S>::new(&input) {
+                Err(error) => {
+                    eprintln!("{error}");
+                    Outcome::Malformed(error)
+                }
+                Ok(report) => {
+                    match args.format {
+                        Format::Json => {
+                            let mut json = report.to_json();
+                            json["input"] = json!(source.to_string());
+                            println!("{json}");
+                        }
+                        Format::Text => print_text(&report),
+                    }
+                    let timings = report.timings;
+                    let total = timings.parse + timings.part_1 + timings.part_2;
+                    Outcome::Solved(total.as_secs_f64() * 1000.0)
+                }
+            },
+        };
+
+        if args.format == Format::Json {
+            let error = match &outcome {
+                Outcome::Solved(_) => None,
+                Outcome::Unreadable(error) => Some(error.to_string()),
+                Outcome::Malformed(error) => Some(summarise(error)),
+            };
+            if let Some(error) = error {
+                println!(
+                    "{}",
+                    json!({"day": S::DAY, "input": source.to_string(), "error": error})
+                );
+            }
+        }
+        outcomes.push(outcome);
+    }
+
+    let solved = outcomes
+        .iter()
+        .filter(|outcome| matches!(outcome, Outcome::Solved(_)))
+        .count();
+
+    if several && args.format == Format::Text {
+        println!("\nSolved {solved} of {} inputs:", sources.len());
+        for (source, outcome) in sources.iter().zip(&outcomes) {
+            match outcome {
+                Outcome::Solved(ms) => println!("  {source}: ok in {ms:.2} ms"),
+                Outcome::Unreadable(error) => println!("  {source}: couldn't read it ({error})"),
+                Outcome::Malformed(error) => println!("  {source}: {}", summarise(error)),
+            }
+        }
+    }
+
+    if solved < sources.len() {
+        process::exit(1);
+    }
+}
+
+// Runs one of a binary's own modes, which print something other than the
+// answers, on every input named on the command line. `each` prints what it
+// makes of an input or returns why it couldn't, which goes to stderr. With
+// more than one input, each input's text output gets a header as it does with
+// solve_inputs. Exits with 1 if any input failed.
+pub fn for_each_source(args: &Args, mut each: impl FnMut(&Source) -> Result<(), String>) {
+    let several = args.several_inputs();
+    let mut failed = false;
+
+    for (i, source) in args.sources().iter().enumerate() {
+        if several && args.format == Format::Text {
+            let gap = if i > 0 { "\n" } else { "" };
+            println!("{gap}==> {source} <==");
+        }
+        if let Err(error) = each(source) {
+            eprintln!("{error}");
+            failed = true;
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+// Like for_each_source, with each input read whole before it's handed over.
+pub fn for_each_input(args: &Args, mut each: impl FnMut(&Source, &str) -> Result<(), String>) {
+    for_each_source(args, |source| {
+        let input = source
+            .read()
+            .map_err(|error| format!("failed to read {source}: {error}"))?;
+        each(source, &input)
+    });
+}
+
+// the parse error on one line, without the caret diagnostic
+fn summarise(error: &ParseError) -> String {
+    let ParseError {
+        line,
+        column,
+        message,
+        ..
+    } = error;
+    format!("{message} (line {line}, column {column})")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_sources() {
+        assert_eq!(Source::expand(Path::new("-")).unwrap(), [Source::Stdin]);
+
+        let file = Path::new("missing.txt");
+        assert_eq!(
+            Source::expand(file).unwrap(),
+            [Source::File(file.to_path_buf())]
+        );
+
+        let directory = std::env::temp_dir().join("aoc-common-expand-sources");
+        fs::create_dir_all(directory.join("nested.txt")).unwrap();
+        for name in ["b.txt", "a.txt", "notes.md"] {
+            fs::write(directory.join(name), "").unwrap();
+        }
+        let names: Vec<String> = Source::expand(&directory)
+            .unwrap()
+            .iter()
+            .map(|source| match source {
+                Source::File(path) => path.file_name().unwrap().to_string_lossy().into_owned(),
+                Source::Stdin => unreachable!(),
+            })
+            .collect();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(names, ["a.txt", "b.txt"]);
+    }
+}
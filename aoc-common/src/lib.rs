@@ -0,0 +1,67 @@
+mod cli;
+mod direction;
+mod error;
+mod grid;
+mod inputs;
+mod point;
+mod report;
+mod rng;
+
+pub use cli::{Args, Format};
+pub use direction::Direction;
+pub use error::{parse_number, ParseError};
+pub use grid::Grid;
+pub use inputs::{for_each_input, for_each_source, solve_inputs, Source};
+pub use point::{Point, Point3};
+pub use report::{Answer, Report, Timings};
+pub use rng::Rng;
+
+use std::fmt::{self, Display};
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+// The shape every day's solution takes: parse the puzzle input once into a
+// typed `Input` and then solve both parts from it. Inputs may borrow from the
+// text they were parsed from, hence the lifetime on `Input`.
+pub trait Solution {
+    const DAY: usize;
+
+    type Input<'a>;
+    type Part1: Answer;
+    type Part2: Answer;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>>;
+    fn part_1(input: &Self::Input<'_>) -> Self::Part1;
+    fn part_2(input: &Self::Input<'_>) -> Self::Part2;
+}
+
+// An answer the solver might not find for a given input, such as a route
+// that doesn't exist or a marker that never appears.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Maybe<T>(pub Option<T>);
+
+impl<T: Display> Display for Maybe<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            Some(answer) => answer.fmt(f),
+            None => write!(f, "not found"),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Maybe<T> {
+    fn from(answer: Option<T>) -> Self {
+        Self(answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_maybe() {
+        assert_eq!(Maybe(Some(42)).to_string(), "42");
+        assert_eq!(Maybe::<usize>(None).to_string(), "not found");
+    }
+}
@@ -0,0 +1,233 @@
+use crate::Direction;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+// A position or offset on a 2D grid. Like the rows of a puzzle input, y grows
+// downwards, so (0, 0) is the top left of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> isize {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    // the number of king's moves between the points, diagonals included
+    pub fn chebyshev_distance(&self, other: &Self) -> isize {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    // each coordinate as -1, 0 or 1, i.e. a single step towards this offset
+    pub fn signum(&self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    // the points sharing an edge with this one, in the order of Direction::ALL
+    pub fn neighbours(&self) -> [Self; 4] {
+        Direction::ALL.map(|direction| *self + direction)
+    }
+
+    // the points sharing an edge or a corner with this one, clockwise from up
+    pub fn neighbours_8(&self) -> [Self; 8] {
+        [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ]
+        .map(|(x, y)| *self + Self::new(x, y))
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Self;
+
+    fn add(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Self;
+
+    fn mul(self, n: isize) -> Self {
+        Self::new(self.x * n, self.y * n)
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+// A position or offset in 3D space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+impl Point3 {
+    pub const ORIGIN: Self = Self::new(0, 0, 0);
+
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> isize {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    // the points sharing a face with this one: +x, -x, +y, -y, +z, then -z
+    pub fn neighbours(&self) -> [Self; 6] {
+        [
+            (1, 0, 0),
+            (-1, 0, 0),
+            (0, 1, 0),
+            (0, -1, 0),
+            (0, 0, 1),
+            (0, 0, -1),
+        ]
+        .map(|(x, y, z)| *self + Self::new(x, y, z))
+    }
+}
+
+impl Add for Point3 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<isize> for Point3 {
+    type Output = Self;
+
+    fn mul(self, n: isize) -> Self {
+        Self::new(self.x * n, self.y * n, self.z * n)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let point = Point::new(3, -2);
+        assert_eq!(point + Point::new(1, 1), Point::new(4, -1));
+        assert_eq!(point - Point::new(1, 1), Point::new(2, -3));
+        assert_eq!(point * 2, Point::new(6, -4));
+        assert_eq!(-point, Point::new(-3, 2));
+        assert_eq!(point + Direction::Up, Point::new(3, -3));
+        assert_eq!(point.signum(), Point::new(1, -1));
+
+        let point = Point3::new(1, 2, 3);
+        assert_eq!(
+            point + Point3::new(1, 1, 1) - Point3::ORIGIN,
+            Point3::new(2, 3, 4)
+        );
+        assert_eq!(-point * 2, Point3::new(-2, -4, -6));
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!(Point::new(8, 7).manhattan_distance(&Point::new(2, 10)), 9);
+        assert_eq!(Point::new(8, 7).chebyshev_distance(&Point::new(2, 10)), 6);
+        assert_eq!(Point3::ORIGIN.manhattan_distance(&Point3::new(1, -2, 3)), 6);
+    }
+
+    #[test]
+    fn neighbours() {
+        let point = Point::new(1, 1);
+        assert_eq!(
+            point.neighbours(),
+            [
+                Point::new(1, 0),
+                Point::new(2, 1),
+                Point::new(1, 2),
+                Point::new(0, 1)
+            ]
+        );
+        assert!(point
+            .neighbours_8()
+            .iter()
+            .all(|n| n.chebyshev_distance(&point) == 1));
+        assert!(Point3::ORIGIN
+            .neighbours()
+            .iter()
+            .all(|n| n.manhattan_distance(&Point3::ORIGIN) == 1));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{Maybe, Result, Solution};
+use serde_json::{json, Value};
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+// An answer to one part of a puzzle, which can be shown to people with
+// `Display` or handed to other tools as JSON.
+pub trait Answer: Display {
+    fn to_json(&self) -> Value;
+}
+
+impl Answer for usize {
+    fn to_json(&self) -> Value {
+        json!(self)
+    }
+}
+
+impl Answer for isize {
+    fn to_json(&self) -> Value {
+        json!(self)
+    }
+}
+
+// Answers spanning several lines (like a CRT screen) become an array of rows.
+impl Answer for String {
+    fn to_json(&self) -> Value {
+        if self.contains('\n') {
+            json!(self.lines().collect::<Vec<_>>())
+        } else {
+            json!(self)
+        }
+    }
+}
+
+impl<T: Answer> Answer for Maybe<T> {
+    fn to_json(&self) -> Value {
+        match &self.0 {
+            Some(answer) => answer.to_json(),
+            None => Value::Null,
+        }
+    }
+}
+
+// How long each step of solving a puzzle took.
+#[derive(Debug, Clone, Copy)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part_1: Duration,
+    pub part_2: Duration,
+}
+
+// The answers to both parts of a puzzle and how long it took to find them.
+pub struct Report<S: Solution> {
+    pub part_1: S::Part1,
+    pub part_2: S::Part2,
+    pub timings: Timings,
+}
+
+impl<S: Solution> Report<S> {
+    pub fn new(input: &str) -> Result<Self> {
+        let (input, parse) = timed(|| S::parse(input));
+        let input = input?;
+        let (part_1, part_1_time) = timed(|| S::part_1(&input));
+        let (part_2, part_2_time) = timed(|| S::part_2(&input));
+
+        Ok(Self {
+            part_1,
+            part_2,
+            timings: Timings {
+                parse,
+                part_1: part_1_time,
+                part_2: part_2_time,
+            },
+        })
+    }
+
+    // {"day":N,"part_1":…,"part_2":…,"timings_ms":{"parse":…,"part_1":…,"part_2":…}}
+    pub fn to_json(&self) -> Value {
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        json!({
+            "day": S::DAY,
+            "part_1": self.part_1.to_json(),
+            "part_2": self.part_2.to_json(),
+            "timings_ms": {
+                "parse": ms(self.timings.parse),
+                "part_1": ms(self.timings.part_1),
+                "part_2": ms(self.timings.part_2),
+            },
+        })
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Example;
+
+    impl Solution for Example {
+        const DAY: usize = 0;
+
+        type Input<'a> = Vec<&'a str>;
+        type Part1 = Maybe<usize>;
+        type Part2 = String;
+
+        fn parse(input: &str) -> Result<Self::Input<'_>> {
+            Ok(input.lines().collect())
+        }
+
+        fn part_1(_: &Vec<&str>) -> Maybe<usize> {
+            Maybe(None)
+        }
+
+        fn part_2(lines: &Vec<&str>) -> String {
+            lines.join("\n")
+        }
+    }
+
+    #[test]
+    fn report_as_json() {
+        let report = Report::<Example>::new("#..\n.#.\n").unwrap();
+        let json = report.to_json();
+
+        assert_eq!(json["day"], json!(0));
+        assert_eq!(json["part_1"], Value::Null);
+        assert_eq!(json["part_2"], json!(["#..", ".#."]));
+        assert!(json["timings_ms"]["parse"].is_f64());
+    }
+
+    #[test]
+    fn answers_as_json() {
+        assert_eq!(42usize.to_json(), json!(42));
+        assert_eq!((-3isize).to_json(), json!(-3));
+        assert_eq!("CMZ".to_string().to_json(), json!("CMZ"));
+        assert_eq!(Maybe(Some(7usize)).to_json(), json!(7));
+    }
+}
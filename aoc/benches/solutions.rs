@@ -0,0 +1,56 @@
+use aoc_common::Solution;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::fs;
+use std::path::Path;
+
+// days whose parts take long enough that a handful of samples is plenty
+const SLOW_DAYS: [usize; 3] = [15, 16, 17];
+
+// Benchmarks parsing and each part separately for every day on its
+// input.txt, as groups named after the day's directory (e.g. day-07/parse).
+// Save a baseline with `cargo bench -p aoc -- --save-baseline <name>` and
+// compare later runs against it with `cargo run -p aoc -- compare <name>`.
+fn bench_day<S: Solution>(c: &mut Criterion) {
+    let name = format!("day-{:02}", S::DAY);
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(&name)
+        .join("input.txt");
+    let Ok(input) = fs::read_to_string(&path) else {
+        eprintln!("skipping {name}: failed to read {}", path.display());
+        return;
+    };
+    let parsed = S::parse(&input).expect("failed to parse input");
+
+    let mut group = c.benchmark_group(&name);
+    if SLOW_DAYS.contains(&S::DAY) {
+        group.sample_size(10);
+    }
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&input))));
+    group.bench_function("part_1", |b| b.iter(|| S::part_1(black_box(&parsed))));
+    group.bench_function("part_2", |b| b.iter(|| S::part_2(black_box(&parsed))));
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_day::<day_01::Day01>,
+    bench_day::<day_02::Day02>,
+    bench_day::<day_03::Day03>,
+    bench_day::<day_04::Day04>,
+    bench_day::<day_05::Day05>,
+    bench_day::<day_06::Day06>,
+    bench_day::<day_07::Day07>,
+    bench_day::<day_08::Day08>,
+    bench_day::<day_09::Day09>,
+    bench_day::<day_10::Day10>,
+    bench_day::<day_11::Day11>,
+    bench_day::<day_12::Day12>,
+    bench_day::<day_13::Day13>,
+    bench_day::<day_14::Day14>,
+    bench_day::<day_15::Day15>,
+    bench_day::<day_16::Day16>,
+    bench_day::<day_17::Day17>,
+    bench_day::<day_18::Day18>,
+);
+criterion_main!(benches);
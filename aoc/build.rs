@@ -0,0 +1,49 @@
+// Generates a test for every input recorded in answers.json, which
+// src/verify.rs includes.
+
+#[path = "src/answers.rs"]
+mod answers;
+
+use answers::Answers;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let answers_path = Path::new(&manifest_dir).join("../answers.json");
+    println!("cargo:rerun-if-changed={}", answers_path.display());
+    println!("cargo:rerun-if-changed=src/answers.rs");
+
+    let answers = Answers::load(&answers_path).expect("failed to read answers.json");
+    let mut tests = String::new();
+
+    for (day_key, inputs) in &answers.0 {
+        let day: usize = day_key
+            .trim_start_matches("day-")
+            .parse()
+            .unwrap_or_else(|_| panic!("unexpected day {day_key:?} in answers.json"));
+
+        for (input_filename, recorded) in inputs {
+            let name: String = format!("{day_key}_{input_filename}")
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+
+            writeln!(tests, "#[test]").unwrap();
+            if let Some(reason) = &recorded.ignore {
+                writeln!(tests, "#[ignore = {reason:?}]").unwrap();
+            }
+            writeln!(
+                tests,
+                "fn {name}() {{\n    check({day}, {input_filename:?}, [{:?}, {:?}]);\n}}\n",
+                recorded.part_1, recorded.part_2
+            )
+            .unwrap();
+        }
+    }
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("answer_tests.rs"), tests).unwrap();
+}
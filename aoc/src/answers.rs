@@ -0,0 +1,50 @@
+// The registry of known answers in answers.json at the root of the workspace,
+// keyed by day directory and then by input file (relative to that directory):
+//
+// {
+//   "day-01": {
+//     "input.txt": { "part_1": "66306", "part_2": "195292" }
+//   }
+// }
+//
+// Answers are stored exactly as the solutions display them. An input can be
+// given an "ignore" reason (e.g. it takes minutes) to skip its generated test
+// by default; recording new answers keeps the reason.
+//
+// This file is also compiled into the build script, which generates a test
+// for every recorded input, so it only depends on serde.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(pub BTreeMap<String, BTreeMap<String, Recorded>>);
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Recorded {
+    pub part_1: String,
+    pub part_2: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore: Option<String>,
+}
+
+impl Answers {
+    // a missing file is just an empty registry
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(io::Error::other),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    #[allow(dead_code)] // unused by the build script
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json + "\n")
+    }
+}
@@ -0,0 +1,90 @@
+use crate::workspace_root;
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+const STEPS: [&str; 3] = ["parse", "part_1", "part_2"];
+
+// slowdowns beyond this are flagged in the table
+const REGRESSION_THRESHOLD: f64 = 5.0;
+
+// where criterion keeps its results, one directory per benchmark and baseline
+fn criterion_directory() -> PathBuf {
+    let target = env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| workspace_root().join("target"));
+    target.join("criterion")
+}
+
+// the mean time in nanoseconds that criterion estimated for one step of a day
+fn mean_nanoseconds(day: usize, step: &str, baseline: &str) -> Option<f64> {
+    let path = criterion_directory()
+        .join(format!("day-{day:02}"))
+        .join(step)
+        .join(baseline)
+        .join("estimates.json");
+    let estimates: Value = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    estimates["mean"]["point_estimate"].as_f64()
+}
+
+fn format_time(nanoseconds: f64) -> String {
+    match nanoseconds {
+        ns if ns >= 1e9 => format!("{:.2} s", ns / 1e9),
+        ns if ns >= 1e6 => format!("{:.2} ms", ns / 1e6),
+        ns if ns >= 1e3 => format!("{:.2} µs", ns / 1e3),
+        ns => format!("{ns:.0} ns"),
+    }
+}
+
+// prints a table comparing two baselines saved by the solutions benchmark,
+// where "new" is criterion's name for the latest run
+pub fn compare(days: &[usize], baseline: &str, other: &str) -> bool {
+    println!(
+        "{:<8}{:<8}{:>12}{:>12}{:>10}",
+        "day", "step", baseline, other, "change"
+    );
+
+    let mut found_any = false;
+    for &day in days {
+        for step in STEPS {
+            let before = mean_nanoseconds(day, step, baseline);
+            let after = mean_nanoseconds(day, step, other);
+            if before.is_none() && after.is_none() {
+                continue;
+            }
+            found_any = true;
+
+            let time = |ns: Option<f64>| ns.map_or("-".to_string(), format_time);
+            let change = match (before, after) {
+                (Some(before), Some(after)) => {
+                    let percent = (after - before) / before * 100.0;
+                    let flag = if percent > REGRESSION_THRESHOLD {
+                        " !"
+                    } else {
+                        ""
+                    };
+                    format!("{percent:+.1}%{flag}")
+                }
+                _ => "-".to_string(),
+            };
+            println!(
+                "{:<8}{:<8}{:>12}{:>12}{:>10}",
+                day,
+                step,
+                time(before),
+                time(after),
+                change
+            );
+        }
+    }
+
+    if !found_any {
+        eprintln!(
+            "no benchmark results for {baseline} or {other} in {}; \
+             run `cargo bench -p aoc -- --save-baseline {baseline}` first",
+            criterion_directory().display()
+        );
+    }
+    found_any
+}
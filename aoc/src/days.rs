@@ -0,0 +1,53 @@
+use aoc_common::{Result, Rng, Solution};
+
+pub type Runner = fn(&str) -> Result<[String; 2]>;
+
+pub const DAYS: [Runner; 18] = [
+    run::<day_01::Day01>,
+    run::<day_02::Day02>,
+    run::<day_03::Day03>,
+    run::<day_04::Day04>,
+    run::<day_05::Day05>,
+    run::<day_06::Day06>,
+    run::<day_07::Day07>,
+    run::<day_08::Day08>,
+    run::<day_09::Day09>,
+    run::<day_10::Day10>,
+    run::<day_11::Day11>,
+    run::<day_12::Day12>,
+    run::<day_13::Day13>,
+    run::<day_14::Day14>,
+    run::<day_15::Day15>,
+    run::<day_16::Day16>,
+    run::<day_17::Day17>,
+    run::<day_18::Day18>,
+];
+
+// writes a random input for a day, given a seeded rng and a size
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+pub const GENERATORS: [Generator; 18] = [
+    day_01::generator::generate,
+    day_02::generator::generate,
+    day_03::generator::generate,
+    day_04::generator::generate,
+    day_05::generator::generate,
+    day_06::generator::generate,
+    day_07::generator::generate,
+    day_08::generator::generate,
+    day_09::generator::generate,
+    day_10::generator::generate,
+    day_11::generator::generate,
+    day_12::generator::generate,
+    day_13::generator::generate,
+    day_14::generator::generate,
+    day_15::generator::generate,
+    day_16::generator::generate,
+    day_17::generator::generate,
+    day_18::generator::generate,
+];
+
+fn run<S: Solution>(input: &str) -> Result<[String; 2]> {
+    let input = S::parse(input)?;
+    Ok([S::part_1(&input).to_string(), S::part_2(&input).to_string()])
+}
@@ -0,0 +1,244 @@
+// Advent of Code 2022: all days
+// https://adventofcode.com/2022
+// Usage: `cargo run -p aoc -- run <day|all> [input-file|directory|-]...`
+//        `cargo run -p aoc -- test <day|all>`
+//        `cargo run -p aoc -- verify <day|all> [input-file] [--record]`
+//        `cargo run -p aoc -- compare <day|all> <baseline> [other-baseline]`
+//        `cargo run -p aoc -- gen <day> [--seed N] [--size N]`
+
+mod answers;
+mod compare;
+mod days;
+mod verify;
+
+use aoc_common::{Rng, Source};
+use days::{DAYS, GENERATORS};
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+const USAGE: &str = "usage: aoc run <day|all> [input-file|directory|-]...
+       aoc test <day|all>
+       aoc verify <day|all> [input-file] [--record]
+       aoc compare <day|all> <baseline> [other-baseline]
+       aoc gen <day> [--seed N] [--size N]";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let (command, days) = match &args[..] {
+        [command, days, ..] => (command.as_str(), parse_days(days)),
+        _ => exit_with_usage(),
+    };
+
+    // keep going after a failing day so that `all` reports on every day
+    let results: Vec<bool> = match command {
+        "run" => {
+            let inputs = match &args[2..] {
+                [] => vec![String::from("input.txt")],
+                inputs => inputs.to_vec(),
+            };
+            // standard input can only be read once, so every day shares it
+            let stdin = inputs
+                .iter()
+                .any(|input| input == "-")
+                .then(|| io::read_to_string(io::stdin()));
+
+            let results: Vec<bool> = days
+                .iter()
+                .flat_map(|day| run(*day, &inputs, stdin.as_ref()))
+                .collect();
+            if results.len() > 1 {
+                let solved = results.iter().filter(|&&ok| ok).count();
+                println!("Solved {solved} of {} inputs", results.len());
+            }
+            results
+        }
+        "test" => days.iter().map(|day| test(*day)).collect(),
+        "verify" => {
+            let (flags, input_filenames): (Vec<&String>, Vec<&String>) =
+                args[2..].iter().partition(|arg| arg.starts_with("--"));
+            let record = match &flags[..] {
+                [] => false,
+                [flag] if *flag == "--record" => true,
+                _ => exit_with_usage(),
+            };
+            let input_filename = match &input_filenames[..] {
+                [] => None,
+                [input_filename] => Some(input_filename.as_str()),
+                _ => exit_with_usage(),
+            };
+
+            if record {
+                verify::record(&days, input_filename.unwrap_or("input.txt"))
+            } else {
+                verify::verify(&days, input_filename)
+            }
+        }
+        "compare" => match &args[2..] {
+            [baseline] => vec![compare::compare(&days, baseline, "new")],
+            [baseline, other] => vec![compare::compare(&days, baseline, other)],
+            _ => exit_with_usage(),
+        },
+        "gen" => match &days[..] {
+            [day] => vec![generate(*day, &args[2..])],
+            _ => exit_with_usage(),
+        },
+        _ => exit_with_usage(),
+    };
+
+    if results.contains(&false) {
+        process::exit(1);
+    }
+}
+
+fn exit_with_usage() -> ! {
+    eprintln!("{USAGE}");
+    process::exit(2);
+}
+
+// parses "all" or a day number from 1 through 18
+fn parse_days(days: &str) -> Vec<usize> {
+    if days == "all" {
+        return (1..=DAYS.len()).collect();
+    }
+
+    match days.parse() {
+        Ok(day) if (1..=DAYS.len()).contains(&day) => vec![day],
+        _ => {
+            eprintln!("expected a day from 1 to {} or all, got {days}", DAYS.len());
+            exit_with_usage();
+        }
+    }
+}
+
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the aoc crate lives inside the workspace")
+}
+
+fn day_directory(day: usize) -> PathBuf {
+    workspace_root().join(format!("day-{day:02}"))
+}
+
+// input files are looked up relative to the current directory first and
+// then relative to the day's directory, so `aoc run 14 input.txt` just works
+fn resolve_input(day: usize, input_filename: &str) -> PathBuf {
+    let path = PathBuf::from(input_filename);
+    if path.exists() {
+        path
+    } else {
+        day_directory(day).join(path)
+    }
+}
+
+// runs the day on every input, reporting whether each one was solved
+fn run(day: usize, inputs: &[String], stdin: Option<&io::Result<String>>) -> Vec<bool> {
+    let mut sources = vec![];
+    for input in inputs {
+        if input == "-" {
+            sources.push(Source::Stdin);
+            continue;
+        }
+        let path = resolve_input(day, input);
+        match Source::expand(&path) {
+            Ok(expanded) => sources.extend(expanded),
+            Err(error) => {
+                eprintln!("Day {day}: failed to read {}: {error}", path.display());
+                return vec![false];
+            }
+        }
+    }
+
+    let several = sources.len() > 1;
+    sources
+        .iter()
+        .map(|source| {
+            let input = match (source, stdin) {
+                (Source::Stdin, Some(Ok(input))) => Ok(input.clone()),
+                (Source::Stdin, Some(Err(error))) => {
+                    Err(io::Error::new(error.kind(), error.to_string()))
+                }
+                _ => source.read(),
+            };
+            run_one(day, source, input, several)
+        })
+        .collect()
+}
+
+fn run_one(day: usize, source: &Source, input: io::Result<String>, several: bool) -> bool {
+    let input = match input {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Day {day}: failed to read {source}: {error}");
+            return false;
+        }
+    };
+
+    let [part_1, part_2] = match DAYS[day - 1](&input) {
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("Day {day}: failed to parse {source}\n{error}");
+            return false;
+        }
+    };
+
+    if several {
+        println!("Day {day} ({source})");
+    } else {
+        println!("Day {day}");
+    }
+    print_answer("Part 1", &part_1);
+    print_answer("Part 2", &part_2);
+    true
+}
+
+fn print_answer(label: &str, answer: &str) {
+    if answer.contains('\n') {
+        println!("  {label}:");
+        for line in answer.lines() {
+            println!("    {line}");
+        }
+    } else {
+        println!("  {label}: {answer}");
+    }
+}
+
+// prints a random input for the day, the same one every time for a seed
+fn generate(day: usize, flags: &[String]) -> bool {
+    let mut seed = 0;
+    let mut size = 100;
+
+    for pair in flags.chunks(2) {
+        let value = match pair {
+            [_, value] => value.parse().ok(),
+            _ => None,
+        };
+        match (pair[0].as_str(), value) {
+            ("--seed", Some(value)) => seed = value,
+            ("--size", Some(value)) => size = value as usize,
+            _ => exit_with_usage(),
+        }
+    }
+
+    print!("{}", GENERATORS[day - 1](&mut Rng::new(seed), size));
+    true
+}
+
+fn test(day: usize) -> bool {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    let status = Command::new(cargo)
+        .current_dir(workspace_root())
+        .args(["test", "--package", &format!("day-{day:02}")])
+        .status();
+
+    match status {
+        Ok(status) => status.success(),
+        Err(error) => {
+            eprintln!("Day {day}: failed to run cargo test: {error}");
+            false
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::answers::{Answers, Recorded};
+use crate::days::DAYS;
+use crate::{day_directory, resolve_input, workspace_root};
+use std::fs;
+use std::path::PathBuf;
+
+// days are keyed by their directory, e.g. "day-07"
+fn day_key(day: usize) -> String {
+    format!("day-{day:02}")
+}
+
+fn answers_path() -> PathBuf {
+    workspace_root().join("answers.json")
+}
+
+fn load_answers() -> Option<Answers> {
+    match Answers::load(&answers_path()) {
+        Ok(answers) => Some(answers),
+        Err(error) => {
+            eprintln!("failed to read {}: {error}", answers_path().display());
+            None
+        }
+    }
+}
+
+// runs a day on one of its inputs, reporting any failure
+fn solve(day: usize, input_filename: &str) -> Option<[String; 2]> {
+    let path = resolve_input(day, input_filename);
+    let input = match fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Day {day}: failed to read {}: {error}", path.display());
+            return None;
+        }
+    };
+
+    match DAYS[day - 1](&input) {
+        Ok(answers) => Some(answers),
+        Err(error) => {
+            eprintln!("Day {day}: failed to parse {}\n{error}", path.display());
+            None
+        }
+    }
+}
+
+// checks each day against every recorded input, or just the given one
+pub fn verify(days: &[usize], input_filename: Option<&str>) -> Vec<bool> {
+    let Some(answers) = load_answers() else {
+        return vec![false];
+    };
+
+    let mut results = vec![];
+    for &day in days {
+        let Some(inputs) = answers.0.get(&day_key(day)) else {
+            println!("Day {day}: no recorded answers");
+            continue;
+        };
+
+        for (recorded_filename, recorded) in inputs {
+            if input_filename.is_some_and(|filename| filename != recorded_filename) {
+                continue;
+            }
+            results.push(check(day, recorded_filename, recorded));
+        }
+    }
+    results
+}
+
+fn check(day: usize, input_filename: &str, recorded: &Recorded) -> bool {
+    let Some([part_1, part_2]) = solve(day, input_filename) else {
+        return false;
+    };
+
+    let mut ok = true;
+    for (part, expected, actual) in [(1, &recorded.part_1, part_1), (2, &recorded.part_2, part_2)] {
+        if *expected != actual {
+            println!(
+                "Day {day} {input_filename} part {part}: expected {expected:?}, got {actual:?}"
+            );
+            ok = false;
+        }
+    }
+
+    if ok {
+        println!("Day {day} {input_filename}: ok");
+    }
+    ok
+}
+
+// runs each day on the given input and saves the answers to the registry
+pub fn record(days: &[usize], input_filename: &str) -> Vec<bool> {
+    let Some(mut answers) = load_answers() else {
+        return vec![false];
+    };
+
+    let mut results = vec![];
+    for &day in days {
+        // inputs are recorded relative to the day's directory so that
+        // verifying doesn't depend on where it's run from
+        let path = resolve_input(day, input_filename);
+        let relative = path.canonicalize().ok().and_then(|path| {
+            let directory = day_directory(day).canonicalize().ok()?;
+            let relative = path.strip_prefix(directory).ok()?;
+            relative.to_str().map(String::from)
+        });
+        let Some(relative) = relative else {
+            eprintln!(
+                "Day {day}: {} must be a file in {}",
+                path.display(),
+                day_directory(day).display()
+            );
+            results.push(false);
+            continue;
+        };
+
+        let Some([part_1, part_2]) = solve(day, &relative) else {
+            results.push(false);
+            continue;
+        };
+
+        let inputs = answers.0.entry(day_key(day)).or_default();
+        let ignore = inputs
+            .get(&relative)
+            .and_then(|recorded| recorded.ignore.clone());
+        let recorded = Recorded {
+            part_1,
+            part_2,
+            ignore,
+        };
+
+        match inputs.insert(relative.clone(), recorded.clone()) {
+            Some(previous) if previous == recorded => println!("Day {day} {relative}: unchanged"),
+            Some(_) => println!("Day {day} {relative}: updated"),
+            None => println!("Day {day} {relative}: recorded"),
+        }
+        results.push(true);
+    }
+
+    if let Err(error) = answers.save(&answers_path()) {
+        eprintln!("failed to write {}: {error}", answers_path().display());
+        results.push(false);
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(day: usize, input_filename: &str, expected: [&str; 2]) {
+        let path = day_directory(day).join(input_filename);
+        let input = fs::read_to_string(&path).expect("failed to read input file");
+        let answers = DAYS[day - 1](&input).expect("failed to parse input");
+        assert_eq!(answers, expected, "day {day} {input_filename}");
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/answer_tests.rs"));
+}
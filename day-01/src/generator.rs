@@ -0,0 +1,32 @@
+// Generates inputs in the puzzle's format: a block of calorie counts per elf,
+// separated by blank lines. `size` is the number of elves.
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let elves: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let items: Vec<String> = (0..rng.range(1..=15))
+                .map(|_| rng.range(1000..=60_000).to_string())
+                .collect();
+            items.join("\n")
+        })
+        .collect();
+
+    elves.join("\n\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day01;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 50);
+            let calories = Day01::parse(&input).unwrap();
+            assert_eq!(calories.len(), 50);
+        }
+    }
+}
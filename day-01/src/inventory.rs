@@ -0,0 +1,258 @@
+use crate::Day01;
+use aoc_common::{parse_number, ParseError, Result, Solution};
+use std::fmt::{self, Display};
+
+// Every elf's items, in the order the elves appear in the input, along with
+// what each elf is carrying in total.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Inventory {
+    items: Vec<Vec<usize>>,
+    totals: Vec<usize>,
+}
+
+// A range of totals in a histogram, and how many elves carry that much.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Bucket {
+    pub from: usize,
+    pub to: usize,
+    pub elves: usize,
+}
+
+// An elf carrying unusually much or little, numbered from 1.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Outlier {
+    pub elf: usize,
+    pub calories: usize,
+}
+
+impl Inventory {
+    // a block of calorie counts per elf, one per line, separated by blank lines
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut items = vec![];
+        let mut totals = vec![];
+
+        for block in input.trim().split("\n\n") {
+            let calories = block
+                .split('\n')
+                .map(|calories| parse_number::<usize>(Day01::DAY, input, calories))
+                .collect::<Result<Vec<_>>>()?;
+            let total = calories
+                .iter()
+                .try_fold(0usize, |total, &calories| total.checked_add(calories))
+                .ok_or_else(|| {
+                    let message = "these calories add up to more than we can count";
+                    ParseError::new(Day01::DAY, input, block, message)
+                })?;
+            items.push(calories);
+            totals.push(total);
+        }
+
+        Ok(Self { items, totals })
+    }
+
+    // there's always at least one elf, since an empty input doesn't parse
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn items(&self, elf: usize) -> &[usize] {
+        &self.items[elf]
+    }
+
+    pub fn totals(&self) -> &[usize] {
+        &self.totals
+    }
+
+    pub fn item_counts(&self) -> Vec<usize> {
+        self.items.iter().map(Vec::len).collect()
+    }
+
+    pub fn min(&self) -> usize {
+        self.totals.iter().copied().min().unwrap_or(0)
+    }
+
+    pub fn max(&self) -> usize {
+        self.totals.iter().copied().max().unwrap_or(0)
+    }
+
+    pub fn mean(&self) -> f64 {
+        let sum: u128 = self.totals.iter().map(|&total| total as u128).sum();
+        sum as f64 / self.len().max(1) as f64
+    }
+
+    pub fn median(&self) -> f64 {
+        percentile(&self.sorted_totals(), 0.5)
+    }
+
+    fn sorted_totals(&self) -> Vec<usize> {
+        let mut sorted = self.totals.clone();
+        sorted.sort_unstable();
+        sorted
+    }
+
+    // Splits the range from the smallest to the largest total into (at most)
+    // `buckets` equal ranges and counts the elves in each.
+    pub fn histogram(&self, buckets: usize) -> Vec<Bucket> {
+        let (min, max) = (self.min(), self.max());
+        let width = (max - min) / buckets.max(1) + 1;
+        let mut histogram: Vec<Bucket> = (min..=max)
+            .step_by(width)
+            .map(|from| Bucket {
+                from,
+                to: from + (width - 1).min(max - from),
+                elves: 0,
+            })
+            .collect();
+
+        for total in &self.totals {
+            histogram[(total - min) / width].elves += 1;
+        }
+        histogram
+    }
+
+    // Elves whose totals lie more than one and a half interquartile ranges
+    // outside the middle half of the totals (Tukey's fences), in input order.
+    pub fn outliers(&self) -> Vec<Outlier> {
+        let sorted = self.sorted_totals();
+        let (q1, q3) = (percentile(&sorted, 0.25), percentile(&sorted, 0.75));
+        let fence = 1.5 * (q3 - q1);
+        let usual = q1 - fence..=q3 + fence;
+
+        self.totals
+            .iter()
+            .enumerate()
+            .filter(|(_, &calories)| !usual.contains(&(calories as f64)))
+            .map(|(i, &calories)| Outlier {
+                elf: i + 1,
+                calories,
+            })
+            .collect()
+    }
+}
+
+// the value `p` of the way through the sorted values, interpolating between
+// neighbours
+fn percentile(sorted: &[usize], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let position = p * (sorted.len() - 1) as f64;
+    let (below, above) = (position.floor() as usize, position.ceil() as usize);
+    let fraction = position - below as f64;
+    sorted[below] as f64 + (sorted[above] as f64 - sorted[below] as f64) * fraction
+}
+
+// The --stats report.
+impl Display for Inventory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const BAR_WIDTH: usize = 40;
+
+        let counts = self.item_counts();
+        writeln!(f, "Elves: {}", self.len())?;
+        writeln!(
+            f,
+            "Calories carried: min {}, max {}, mean {:.1}, median {:.1}",
+            self.min(),
+            self.max(),
+            self.mean(),
+            self.median()
+        )?;
+        writeln!(
+            f,
+            "Items carried: min {}, max {}, total {}",
+            counts.iter().min().unwrap_or(&0),
+            counts.iter().max().unwrap_or(&0),
+            counts.iter().sum::<usize>()
+        )?;
+
+        let histogram = self.histogram(10);
+        let most = histogram
+            .iter()
+            .map(|bucket| bucket.elves)
+            .max()
+            .unwrap_or(0);
+        let label_width = self.max().to_string().len();
+        writeln!(f, "Totals:")?;
+        for Bucket { from, to, elves } in histogram {
+            let bar = "#".repeat((elves * BAR_WIDTH).div_ceil(most.max(1)));
+            writeln!(
+                f,
+                "  {from:>label_width$}-{to:<label_width$} |{bar} {elves}"
+            )?;
+        }
+
+        let outliers = self.outliers();
+        if outliers.is_empty() {
+            write!(f, "Outliers: none")
+        } else {
+            let outliers: Vec<String> = outliers
+                .iter()
+                .map(|Outlier { elf, calories }| format!("elf {elf} ({calories})"))
+                .collect();
+            write!(f, "Outliers: {}", outliers.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_statistics() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let inventory = Inventory::parse(&input).unwrap();
+
+        assert_eq!(inventory.len(), 5);
+        assert_eq!(inventory.totals(), [6000, 4000, 11000, 24000, 10000]);
+        assert_eq!(inventory.item_counts(), [3, 1, 2, 3, 1]);
+        assert_eq!(inventory.items(2), [5000, 6000]);
+        assert_eq!((inventory.min(), inventory.max()), (4000, 24000));
+        assert_eq!(inventory.mean(), 11000.0);
+        assert_eq!(inventory.median(), 10000.0);
+        assert_eq!(
+            inventory.outliers(),
+            [Outlier {
+                elf: 4,
+                calories: 24000
+            }]
+        );
+    }
+
+    #[test]
+    fn test_histogram() {
+        let inventory = Inventory::parse("1\n\n2\n\n3\n\n10\n\n10").unwrap();
+        let histogram = inventory.histogram(3);
+        let elves: Vec<usize> = histogram.iter().map(|bucket| bucket.elves).collect();
+        assert_eq!(elves, [3, 0, 2]);
+        assert_eq!((histogram[0].from, histogram[0].to), (1, 4));
+        assert_eq!((histogram[2].from, histogram[2].to), (9, 10));
+
+        // one elf, or every elf carrying the same, is a single bucket
+        let inventory = Inventory::parse("7\n\n3\n4").unwrap();
+        assert_eq!(
+            inventory.histogram(10),
+            [Bucket {
+                from: 7,
+                to: 7,
+                elves: 2
+            }]
+        );
+        assert_eq!(inventory.median(), 7.0);
+        assert!(inventory.outliers().is_empty());
+    }
+
+    #[test]
+    fn test_report() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let report = Inventory::parse(&input).unwrap().to_string();
+        assert!(report.starts_with("Elves: 5\n"));
+        assert!(report.contains("min 4000, max 24000, mean 11000.0, median 10000.0"));
+        assert!(report.ends_with("Outliers: elf 4 (24000)"));
+    }
+}
@@ -0,0 +1,84 @@
+pub mod generator;
+pub mod inventory;
+pub mod top;
+
+use aoc_common::{Result, Solution};
+use inventory::Inventory;
+
+pub fn ordered_calories(input: &str) -> Result<Vec<usize>> {
+    let mut ordered_calories = Inventory::parse(input)?.totals().to_vec();
+    ordered_calories.sort_by(|a, b| b.cmp(a));
+    Ok(ordered_calories)
+}
+
+pub fn top(ordered_calories: &[usize]) -> usize {
+    ordered_calories[0]
+}
+
+pub fn top_three(ordered_calories: &[usize]) -> usize {
+    ordered_calories.iter().take(3).sum()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: usize = 1;
+
+    type Input<'a> = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        ordered_calories(input)
+    }
+
+    fn part_1(ordered_calories: &Vec<usize>) -> usize {
+        top(ordered_calories)
+    }
+
+    fn part_2(ordered_calories: &Vec<usize>) -> usize {
+        top_three(ordered_calories)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use std::fs;
+
+    #[test]
+    fn test_top_and_top_three() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let ordered_calories = ordered_calories(&input).unwrap();
+        assert_eq!(top(&ordered_calories), 24000);
+        assert_eq!(top_three(&ordered_calories), 45000);
+    }
+
+    #[test]
+    fn test_invalid_calories() {
+        let error = ordered_calories("1000\n2000\n\n3x00\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.snippet, "3x00");
+
+        let error = ordered_calories("1\n\n18446744073709551615\n1\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+    }
+
+    proptest! {
+        #[test]
+        fn calories_are_totalled_and_ordered(elves in vec(vec(0..100_000usize, 1..10), 1..50)) {
+            let blocks: Vec<String> = elves
+                .iter()
+                .map(|items| items.iter().map(|c| c.to_string()).collect::<Vec<_>>().join("\n"))
+                .collect();
+            let ordered = ordered_calories(&blocks.join("\n\n")).unwrap();
+
+            let mut totals: Vec<usize> = elves.iter().map(|items| items.iter().sum()).collect();
+            totals.sort_unstable_by(|a, b| b.cmp(a));
+            prop_assert_eq!(&ordered, &totals);
+            prop_assert!(top_three(&ordered) >= top(&ordered));
+        }
+    }
+}
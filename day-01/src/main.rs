@@ -1,30 +1,114 @@
 // Advent of Code 2022: Day 1
 // https://adventofcode.com/2022/day/1
-// Usage: `cargo run <input-file>`
+// Usage: `cargo run <input-file|directory|->... [--format text|json] [--top K] [--stats]`
+//
+// With `--top K` the inputs are streamed rather than read whole, so they can
+// be as big as you like, and the K elves carrying the most are listed. With
+// `--stats` there's a report on the whole inventory instead of the answers.
 
-use std::env;
-use std::fs;
+use aoc_common::{for_each_input, for_each_source, solve_inputs, Args, Format};
+use day_01::inventory::{Bucket, Inventory, Outlier};
+use day_01::top::{top_k, Elf};
+use day_01::Day01;
+use serde_json::json;
+use std::process;
+
+const FLAGS: [&str; 2] = ["--top K", "--stats"];
 
 fn main() {
-    let input_filename = env::args().nth(1).expect("please supply an input filename");
-    let input = fs::read_to_string(input_filename).expect("failed to read input");
-
-    let mut ordered_calories: Vec<usize> = input
-        .trim()
-        .split("\n\n")
-        .map(|items| {
-            items
-                .split('\n')
-                .map(|calories| calories.parse::<usize>().expect("invalid integer"))
-                .sum()
-        })
-        .collect();
-
-    ordered_calories.sort_by(|a, b| b.cmp(a));
-
-    let top = ordered_calories[0];
-    let top_three: usize = ordered_calories.into_iter().take(3).sum();
-
-    println!("The elf with the most calories was carrying: {}", top);
-    println!("The top three combined were carrying: {}", top_three);
+    let args = Args::from_env_with(&FLAGS);
+    if let Err(message) = args.at_most_one_of(&["--top", "--stats"]) {
+        eprintln!("{message}");
+        process::exit(2);
+    }
+
+    if let Some(k) = args.value::<usize>("--top") {
+        print_top_k(&args, k);
+        return;
+    }
+    if args.has("--stats") {
+        print_stats(&args);
+        return;
+    }
+
+    solve_inputs::<Day01>(&args, |report| {
+        println!(
+            "The elf with the most calories was carrying: {}",
+            report.part_1
+        );
+        println!("The top three combined were carrying: {}", report.part_2);
+    });
+}
+
+fn print_top_k(args: &Args, k: usize) {
+    for_each_source(args, |source| {
+        let elves = source
+            .open()
+            .map_err(Into::into)
+            .and_then(|reader| top_k(reader, k))
+            .map_err(|error| format!("{source}: {error}"))?;
+
+        // each elf's calories fit, but they might not all together
+        let total = elves
+            .iter()
+            .try_fold(0usize, |total, elf| total.checked_add(elf.calories))
+            .ok_or_else(|| {
+                format!("{source}: the top {k} elves' calories add up to more than we can count")
+            })?;
+        match args.format {
+            Format::Json => {
+                let elves: Vec<_> = elves
+                    .iter()
+                    .map(|elf| json!({"elf": elf.index, "calories": elf.calories}))
+                    .collect();
+                println!(
+                    "{}",
+                    json!({"day": 1, "input": source.to_string(), "top": elves, "total": total})
+                );
+            }
+            Format::Text => {
+                for (rank, Elf { index, calories }) in elves.iter().enumerate() {
+                    println!("{:>3}. elf {index} is carrying {calories}", rank + 1);
+                }
+                println!("Together they're carrying: {total}");
+            }
+        }
+        Ok(())
+    });
+}
+
+fn print_stats(args: &Args) {
+    for_each_input(args, |source, input| {
+        let inventory = Inventory::parse(input).map_err(|error| error.to_string())?;
+
+        match args.format {
+            Format::Json => {
+                let histogram: Vec<_> = inventory
+                    .histogram(10)
+                    .iter()
+                    .map(|Bucket { from, to, elves }| json!({"from": from, "to": to, "elves": elves}))
+                    .collect();
+                let outliers: Vec<_> = inventory
+                    .outliers()
+                    .iter()
+                    .map(|Outlier { elf, calories }| json!({"elf": elf, "calories": calories}))
+                    .collect();
+                let stats = json!({
+                    "day": 1,
+                    "input": source.to_string(),
+                    "elves": inventory.len(),
+                    "min": inventory.min(),
+                    "max": inventory.max(),
+                    "mean": inventory.mean(),
+                    "median": inventory.median(),
+                    "item_counts": inventory.item_counts(),
+                    "histogram": histogram,
+                    "outliers": outliers,
+                });
+                println!("{stats}");
+            }
+            Format::Text => println!("{inventory}"),
+        }
+        Ok(())
+    });
 }
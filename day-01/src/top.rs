@@ -0,0 +1,175 @@
+use crate::Day01;
+use aoc_common::{parse_number, ParseError, Solution};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::{self, Display};
+use std::io::{self, BufRead};
+
+// An elf's total calories, numbered from 1 in the order the elves appear.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Elf {
+    pub index: usize,
+    pub calories: usize,
+}
+
+// Reading a stream of inventories can fail part way through, either because
+// the reader does or because a line isn't a number.
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read the input: {error}"),
+            Self::Parse(error) => error.fmt(f),
+        }
+    }
+}
+
+impl From<io::Error> for StreamError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+// The `k` elves carrying the most calories, most first (and the earlier elf
+// first on a tie), read a line at a time so that only the current line and
+// `k` elves are ever held in memory.
+pub fn top_k(mut reader: impl BufRead, k: usize) -> Result<Vec<Elf>, StreamError> {
+    // a min-heap on calories, where the later of two equal elves is smaller
+    // so that it's the one that gets dropped
+    let mut heap = BinaryHeap::new();
+    let mut keep = |elf: Elf| {
+        heap.push(Reverse((elf.calories, Reverse(elf.index))));
+        if heap.len() > k {
+            heap.pop();
+        }
+    };
+
+    let mut line = String::new();
+    let mut line_number = 0;
+    let mut index = 0;
+    let mut total = None;
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        line_number += 1;
+
+        let calories = line.trim_end_matches(['\n', '\r']);
+        if calories.is_empty() {
+            if let Some(calories) = total.take() {
+                keep(Elf { index, calories });
+            }
+            continue;
+        }
+
+        let error_here = |mut error: ParseError| {
+            error.line = line_number;
+            StreamError::Parse(error)
+        };
+        let calories = parse_number::<usize>(Day01::DAY, calories, calories).map_err(error_here)?;
+        if total.is_none() {
+            index += 1;
+        }
+        total = match total.unwrap_or(0usize).checked_add(calories) {
+            Some(total) => Some(total),
+            None => {
+                let message = "these calories add up to more than we can count";
+                let line = line.as_str();
+                return Err(error_here(ParseError::new(Day01::DAY, line, line, message)));
+            }
+        };
+    }
+
+    if let Some(calories) = total {
+        keep(Elf { index, calories });
+    }
+
+    Ok(heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((calories, Reverse(index)))| Elf { index, calories })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ordered_calories;
+    use std::fs;
+
+    #[test]
+    fn test_top_k() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let elves = top_k(input.as_bytes(), 3).unwrap();
+        assert_eq!(
+            elves,
+            [
+                Elf {
+                    index: 4,
+                    calories: 24000
+                },
+                Elf {
+                    index: 3,
+                    calories: 11000
+                },
+                Elf {
+                    index: 5,
+                    calories: 10000
+                },
+            ]
+        );
+
+        let everyone = top_k(input.as_bytes(), 10).unwrap();
+        let calories: Vec<usize> = everyone.iter().map(|elf| elf.calories).collect();
+        assert_eq!(calories, ordered_calories(&input).unwrap());
+        assert!(top_k(input.as_bytes(), 0).unwrap().is_empty());
+
+        // asking for more elves than there could ever be doesn't allocate
+        // room for them
+        assert_eq!(top_k(input.as_bytes(), usize::MAX).unwrap(), everyone);
+        assert_eq!(
+            top_k(input.as_bytes(), 1_000_000_000_000).unwrap(),
+            everyone
+        );
+    }
+
+    #[test]
+    fn test_top_k_ties_and_blank_lines() {
+        let elves = top_k("\n5\n\n\n\n2\n3\r\n\r\n4\n1".as_bytes(), 2).unwrap();
+        assert_eq!(
+            elves,
+            [
+                Elf {
+                    index: 1,
+                    calories: 5
+                },
+                Elf {
+                    index: 2,
+                    calories: 5
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_top_k_errors() {
+        let Err(StreamError::Parse(error)) = top_k("1000\n\n3x00\n".as_bytes(), 1) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.snippet, "3x00");
+
+        let input = "1\n\n18446744073709551615\n1\n";
+        let Err(StreamError::Parse(error)) = top_k(input.as_bytes(), 1) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(error.line, 4);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::game::{Game, Outcome};
+use crate::{parse_strategy_guide_part_1, play, Round, RPS};
+use aoc_common::Result;
+use std::cmp::Reverse;
+use std::fmt::{self, Display};
+
+// A reading of the strategy guide's second column: either the shapes X, Y and
+// Z stand for, or the outcomes they ask for. Part 1 reads them as rock, paper
+// and scissors, and part 2 as lose, draw and win.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cipher {
+    Shapes([RPS; 3]),
+    Outcomes([Outcome; 3]),
+}
+
+// How many points a cipher scores on a guide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ranking {
+    pub cipher: Cipher,
+    pub score: usize,
+}
+
+impl Cipher {
+    pub const PART_1: Self = Self::Shapes([RPS::Rock, RPS::Paper, RPS::Scissors]);
+    pub const PART_2: Self = Self::Outcomes([Outcome::Loss, Outcome::Tie, Outcome::Win]);
+
+    // every way X, Y and Z could stand for different shapes, and then for
+    // different outcomes
+    pub fn all() -> Vec<Self> {
+        let shapes = permutations([RPS::Rock, RPS::Paper, RPS::Scissors]).map(Self::Shapes);
+        let outcomes = permutations(Outcome::ALL).map(Self::Outcomes);
+        shapes.chain(outcomes).collect()
+    }
+
+    // the guide's rounds as this cipher reads them
+    pub fn decode(&self, strategy_guide: &str) -> Result<Vec<Round>> {
+        let game = Game::rock_paper_scissors();
+
+        // part 1's reading turns X, Y and Z into rock, paper and scissors,
+        // which we can then read as the first, second or third code
+        let rounds = parse_strategy_guide_part_1(strategy_guide)?;
+        Ok(rounds
+            .into_iter()
+            .map(|(opponent, code)| {
+                let column = code as usize - 1;
+                let player = match self {
+                    Self::Shapes(shapes) => shapes[column],
+                    Self::Outcomes(outcomes) => {
+                        RPS::from_shape(game.shape_for(opponent.shape(), outcomes[column]))
+                    }
+                };
+                (opponent, player)
+            })
+            .collect())
+    }
+}
+
+// like "X=rock Y=paper Z=scissors"
+impl Display for Cipher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let meanings = match self {
+            Self::Shapes(shapes) => shapes.map(|shape| match shape {
+                RPS::Rock => "rock",
+                RPS::Paper => "paper",
+                RPS::Scissors => "scissors",
+            }),
+            Self::Outcomes(outcomes) => outcomes.map(|outcome| match outcome {
+                Outcome::Loss => "lose",
+                Outcome::Tie => "draw",
+                Outcome::Win => "win",
+            }),
+        };
+        write!(f, "X={} Y={} Z={}", meanings[0], meanings[1], meanings[2])
+    }
+}
+
+fn permutations<T: Copy>([a, b, c]: [T; 3]) -> impl Iterator<Item = [T; 3]> {
+    [
+        [a, b, c],
+        [a, c, b],
+        [b, a, c],
+        [b, c, a],
+        [c, a, b],
+        [c, b, a],
+    ]
+    .into_iter()
+}
+
+// Scores the guide under every cipher, from the best score to the worst.
+// Ciphers that score the same stay in the order of Cipher::all.
+pub fn rank_ciphers(strategy_guide: &str) -> Result<Vec<Ranking>> {
+    let mut rankings = Cipher::all()
+        .into_iter()
+        .map(|cipher| {
+            let score = play(strategy_guide, |guide| cipher.decode(guide))?;
+            Ok(Ranking { cipher, score })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    rankings.sort_by_key(|ranking| Reverse(ranking.score));
+    Ok(rankings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_rank_ciphers() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let rankings = rank_ciphers(&input).unwrap();
+        assert_eq!(rankings.len(), 12);
+
+        let score = |cipher| rankings.iter().find(|r| r.cipher == cipher).unwrap().score;
+        assert_eq!(score(Cipher::PART_1), 15);
+        assert_eq!(score(Cipher::PART_2), 12);
+
+        // reading the guide as paper against rock, scissors against paper
+        // and rock against scissors wins every round
+        let best = Cipher::Shapes([RPS::Scissors, RPS::Paper, RPS::Rock]);
+        assert_eq!(rankings[0].cipher, best);
+        assert_eq!(rankings[0].score, (6 + 2) + (6 + 3) + (6 + 1));
+        assert!(rankings.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Cipher::PART_1.to_string(), "X=rock Y=paper Z=scissors");
+        assert_eq!(Cipher::PART_2.to_string(), "X=lose Y=draw Z=win");
+    }
+}
@@ -0,0 +1,298 @@
+use crate::Day02;
+use aoc_common::{ParseError, Result, Solution};
+use std::cmp::Reverse;
+
+// One of a game's shapes, numbered from 0 in the order the game lists them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Shape(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Loss,
+    Tie,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Self; 3] = [Self::Loss, Self::Tie, Self::Win];
+}
+
+// What a round is worth to the player: points for the outcome, plus a bonus
+// for the shape they played.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scoring {
+    pub loss: usize,
+    pub tie: usize,
+    pub win: usize,
+    pub bonuses: Vec<usize>,
+}
+
+impl Scoring {
+    // the puzzle's scoring: 0, 3 or 6 for the outcome, and 1 for the first
+    // shape, 2 for the second and so on
+    pub fn standard(shapes: usize) -> Self {
+        Self {
+            loss: 0,
+            tie: 3,
+            win: 6,
+            bonuses: (1..=shapes).collect(),
+        }
+    }
+
+    pub fn outcome(&self, outcome: Outcome) -> usize {
+        match outcome {
+            Outcome::Loss => self.loss,
+            Outcome::Tie => self.tie,
+            Outcome::Win => self.win,
+        }
+    }
+}
+
+// A game of cyclic dominance: an odd number of shapes arranged in a circle,
+// where each shape beats the half of the others just before it and loses to
+// the half just after. Rock, paper, scissors is the game of three shapes.
+//
+// Strategy guides for a game have an opponent's code in the first column
+// (A, B, C, ... for the shapes in order), and in the second either the
+// player's code (the same number of letters, ending with Z) or the outcome
+// they should aim for (X, Y or Z for a loss, tie or win).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    names: Vec<String>,
+    scoring: Scoring,
+    opponent_codes: Vec<String>,
+    player_codes: Vec<String>,
+}
+
+impl Game {
+    pub fn new(names: &[&str], scoring: Scoring) -> Self {
+        let n = names.len();
+        assert!(n % 2 == 1, "a game needs an odd number of shapes, not {n}");
+        // with one shape there's nothing to win or lose against
+        assert!(n >= 3, "a game needs at least 3 shapes, not {n}");
+        assert!(n <= 13, "there are only enough codes for 13 shapes");
+        assert_eq!(scoring.bonuses.len(), n, "expected a bonus for every shape");
+
+        let codes = |from: u8| (from..from + n as u8).map(|c| (c as char).to_string());
+        Self {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            scoring,
+            opponent_codes: codes(b'A').collect(),
+            player_codes: codes(b'Z' + 1 - n as u8).collect(),
+        }
+    }
+
+    pub fn rock_paper_scissors() -> Self {
+        Self::new(&["rock", "paper", "scissors"], Scoring::standard(3))
+    }
+
+    // ordered so that every shape beats the two before it: Spock smashes
+    // rock and scissors, paper covers rock and disproves Spock, and so on
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        let names = ["rock", "Spock", "paper", "lizard", "scissors"];
+        Self::new(&names, Scoring::standard(5))
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.len()).map(Shape)
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.names[shape.0]
+    }
+
+    pub fn scoring(&self) -> &Scoring {
+        &self.scoring
+    }
+
+    pub fn opponent_codes(&self) -> &[String] {
+        &self.opponent_codes
+    }
+
+    pub fn player_codes(&self) -> &[String] {
+        &self.player_codes
+    }
+
+    // how the round goes for `player`
+    pub fn outcome(&self, player: Shape, opponent: Shape) -> Outcome {
+        let n = self.len();
+        match (player.0 + n - opponent.0) % n {
+            0 => Outcome::Tie,
+            steps if steps <= n / 2 => Outcome::Win,
+            _ => Outcome::Loss,
+        }
+    }
+
+    // The shape that gets the outcome against the opponent. When more than
+    // one does, it's the one with the biggest bonus (and the first of those).
+    pub fn shape_for(&self, opponent: Shape, outcome: Outcome) -> Shape {
+        self.shapes()
+            .filter(|&player| self.outcome(player, opponent) == outcome)
+            .max_by_key(|player| (self.scoring.bonuses[player.0], Reverse(player.0)))
+            .expect("every outcome is possible against every shape")
+    }
+
+    pub fn score_round(&self, (opponent, player): (Shape, Shape)) -> usize {
+        let outcome = self.outcome(player, opponent);
+        self.scoring.outcome(outcome) + self.scoring.bonuses[player.0]
+    }
+
+    pub fn score(&self, rounds: &[(Shape, Shape)]) -> usize {
+        rounds.iter().map(|&round| self.score_round(round)).sum()
+    }
+
+    // a strategy guide whose second column is the shape to play
+    pub fn parse_shapes(&self, input: &str) -> Result<Vec<(Shape, Shape)>> {
+        self.parse_guide(input, |code| {
+            Ok(Shape(parse_code(input, code, &self.player_codes)?))
+        })
+    }
+
+    // a strategy guide whose second column is the outcome to aim for
+    pub fn parse_outcomes(&self, input: &str) -> Result<Vec<(Shape, Shape)>> {
+        self.parse_guide(input, |code| {
+            Ok(Outcome::ALL[parse_code(input, code, &["X", "Y", "Z"])?])
+        })
+        .map(|rounds| {
+            rounds
+                .into_iter()
+                .map(|(opponent, outcome)| (opponent, self.shape_for(opponent, outcome)))
+                .collect()
+        })
+    }
+
+    // Reads each round's columns: the opponent's shape, then whatever
+    // `second` makes of the second column.
+    fn parse_guide<T>(
+        &self,
+        input: &str,
+        second: impl Fn(&str) -> Result<T>,
+    ) -> Result<Vec<(Shape, T)>> {
+        input
+            .trim()
+            .split('\n')
+            .map(|line| {
+                let (opponent, player) = line.split_once(' ').ok_or_else(|| {
+                    let message = "expected two columns separated by a space";
+                    ParseError::new(Day02::DAY, input, line, message)
+                })?;
+                let opponent = Shape(parse_code(input, opponent, &self.opponent_codes)?);
+                Ok((opponent, second(player)?))
+            })
+            .collect()
+    }
+}
+
+// the position of the code in `codes`
+fn parse_code(input: &str, code: &str, codes: &[impl AsRef<str>]) -> Result<usize> {
+    codes
+        .iter()
+        .position(|c| c.as_ref() == code)
+        .ok_or_else(|| {
+            let codes: Vec<&str> = codes.iter().map(AsRef::as_ref).collect();
+            let message = format!("expected one of {}, got {code:?}", codes.join(", "));
+            ParseError::new(Day02::DAY, input, code, message)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_rock_paper_scissors() {
+        let game = Game::rock_paper_scissors();
+        let [rock, paper, scissors] = [0, 1, 2].map(Shape);
+        assert_eq!(game.outcome(paper, rock), Outcome::Win);
+        assert_eq!(game.outcome(rock, scissors), Outcome::Win);
+        assert_eq!(game.outcome(rock, paper), Outcome::Loss);
+        assert_eq!(game.outcome(scissors, scissors), Outcome::Tie);
+        assert_eq!(game.shape_for(rock, Outcome::Loss), scissors);
+
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        assert_eq!(game.score(&game.parse_shapes(&input).unwrap()), 15);
+        assert_eq!(game.score(&game.parse_outcomes(&input).unwrap()), 12);
+    }
+
+    #[test]
+    fn test_rock_paper_scissors_lizard_spock() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let shape = |name| game.shapes().find(|&s| game.name(s) == name).unwrap();
+        let beats = |a, b| game.outcome(shape(a), shape(b)) == Outcome::Win;
+
+        for (winner, loser) in [
+            ("scissors", "paper"),
+            ("paper", "rock"),
+            ("rock", "lizard"),
+            ("lizard", "Spock"),
+            ("Spock", "scissors"),
+            ("scissors", "lizard"),
+            ("lizard", "paper"),
+            ("paper", "Spock"),
+            ("Spock", "rock"),
+            ("rock", "scissors"),
+        ] {
+            assert!(beats(winner, loser), "{winner} beats {loser}");
+            assert!(!beats(loser, winner), "{loser} doesn't beat {winner}");
+        }
+
+        // both lizard and scissors beat paper, and scissors scores more
+        assert_eq!(
+            game.shape_for(shape("paper"), Outcome::Win),
+            shape("scissors")
+        );
+        assert_eq!(game.player_codes(), ["V", "W", "X", "Y", "Z"]);
+
+        // Spock vs rock wins, scissors vs lizard wins
+        let rounds = game.parse_shapes("A W\nD Z\n").unwrap();
+        assert_eq!(game.score(&rounds), (6 + 2) + (6 + 5));
+        let rounds = game.parse_outcomes("E X\n").unwrap();
+        assert_eq!(game.outcome(rounds[0].1, shape("scissors")), Outcome::Loss);
+    }
+
+    #[test]
+    fn test_scoring() {
+        let scoring = Scoring {
+            loss: 1,
+            tie: 2,
+            win: 10,
+            bonuses: vec![0, 0, 100],
+        };
+        let game = Game::new(&["a", "b", "c"], scoring);
+        assert_eq!(game.score_round((Shape(0), Shape(1))), 10);
+        assert_eq!(game.score_round((Shape(1), Shape(2))), 110);
+        assert_eq!(game.score_round((Shape(0), Shape(2))), 101);
+    }
+
+    #[test]
+    fn test_invalid_guides() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let error = game.parse_shapes("A V\nF V").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "expected one of A, B, C, D, E, got \"F\"");
+
+        let error = game.parse_outcomes("A W").unwrap_err();
+        assert_eq!(error.message, "expected one of X, Y, Z, got \"W\"");
+    }
+
+    #[test]
+    #[should_panic(expected = "odd number of shapes")]
+    fn test_even_games() {
+        Game::new(&["odd", "even"], Scoring::standard(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 3 shapes")]
+    fn test_one_shape_games() {
+        Game::new(&["rock"], Scoring::standard(1));
+    }
+}
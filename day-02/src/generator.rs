@@ -0,0 +1,31 @@
+// Generates inputs in the puzzle's format: one round of the strategy guide
+// per line, like "A Y". `size` is the number of rounds.
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.choose(&["A", "B", "C"]),
+                rng.choose(&["X", "Y", "Z"])
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day02;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 100);
+            let [rounds, _] = Day02::parse(&input).unwrap();
+            assert_eq!(rounds.len(), 100);
+        }
+    }
+}
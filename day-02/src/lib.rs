@@ -0,0 +1,124 @@
+pub mod cipher;
+pub mod game;
+pub mod generator;
+pub mod tournament;
+
+use aoc_common::{Result, Solution};
+use game::{Game, Shape};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RPS {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+pub enum RPSResult {
+    Loss = 0,
+    Tie = 3,
+    Win = 6,
+}
+
+impl RPS {
+    // each shape beats the one before it, as in Game::rock_paper_scissors
+    pub fn vs(&self, other: &RPS) -> RPSResult {
+        match (*self as usize + 3 - *other as usize) % 3 {
+            0 => RPSResult::Tie,
+            1 => RPSResult::Win,
+            _ => RPSResult::Loss,
+        }
+    }
+
+    // the same shape in Game::rock_paper_scissors
+    pub fn shape(self) -> Shape {
+        Shape(self as usize - 1)
+    }
+
+    pub fn from_shape(shape: Shape) -> Self {
+        [RPS::Rock, RPS::Paper, RPS::Scissors][shape.0]
+    }
+}
+
+// a round of the strategy guide as (opponent, player)
+pub type Round = (RPS, RPS);
+
+pub fn parse_strategy_guide_part_1(input: &str) -> Result<Vec<Round>> {
+    Ok(rounds(Game::rock_paper_scissors().parse_shapes(input)?))
+}
+
+pub fn parse_strategy_guide_part_2(input: &str) -> Result<Vec<Round>> {
+    Ok(rounds(Game::rock_paper_scissors().parse_outcomes(input)?))
+}
+
+fn rounds(shapes: Vec<(Shape, Shape)>) -> Vec<Round> {
+    shapes
+        .into_iter()
+        .map(|(opponent, player)| (RPS::from_shape(opponent), RPS::from_shape(player)))
+        .collect()
+}
+
+pub fn play(strategy_guide: &str, parse: impl Fn(&str) -> Result<Vec<Round>>) -> Result<usize> {
+    Ok(score(&parse(strategy_guide)?))
+}
+
+pub fn score(rounds: &[Round]) -> usize {
+    let mut score = 0;
+    for (opponent, player) in rounds {
+        score += player.vs(opponent) as usize + *player as usize;
+    }
+    score
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: usize = 2;
+
+    // the strategy guide as read under each part's interpretation of X, Y and Z
+    type Input<'a> = [Vec<Round>; 2];
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok([
+            parse_strategy_guide_part_1(input)?,
+            parse_strategy_guide_part_2(input)?,
+        ])
+    }
+
+    fn part_1([rounds, _]: &[Vec<Round>; 2]) -> usize {
+        score(rounds)
+    }
+
+    fn part_2([_, rounds]: &[Vec<Round>; 2]) -> usize {
+        score(rounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn part_1() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        assert_eq!(play(&input, parse_strategy_guide_part_1).unwrap(), 15);
+    }
+
+    #[test]
+    fn part_2() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        assert_eq!(play(&input, parse_strategy_guide_part_2).unwrap(), 12);
+    }
+
+    #[test]
+    fn invalid_strategy_guide() {
+        let error = parse_strategy_guide_part_2("A Y\nB W\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.message, "expected one of X, Y, Z, got \"W\"");
+
+        let error = parse_strategy_guide_part_1("A Y\nBX").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+}
@@ -1,117 +1,151 @@
 // Advent of Code 2022: Day 2
 // https://adventofcode.com/2022/day/2
-// Usage: `cargo run <input-file>`
+// Usage: `cargo run <input-file|directory|->... [--format text|json] [--ciphers] [--tournament N [--seed N]]`
+//
+// With `--ciphers` every way of reading X, Y and Z is scored and ranked,
+// instead of just the two the puzzle asks about. `--tournament N` plays the
+// guide, its opponent's column and some adaptive players against each other
+// for N rounds a match and prints the league table.
 
-use std::env;
-use std::fs;
+use aoc_common::{for_each_input, solve_inputs, Args, Format};
+use day_02::cipher::{rank_ciphers, Cipher, Ranking};
+use day_02::tournament::{
+    round_robin, CopyLastMove, FixedGuide, FrequencyCounter, LeagueTable, Random, Standing,
+    Strategy,
+};
+use day_02::{parse_strategy_guide_part_1, Day02};
+use serde_json::json;
+use std::process;
+
+const FLAGS: [&str; 3] = ["--ciphers", "--tournament N", "--seed N"];
 
 fn main() {
-    let input_filename = env::args().nth(1).expect("please supply an input filename");
-    let input = fs::read_to_string(input_filename).expect("failed to read input");
+    let args = Args::from_env_with(&FLAGS);
+    check_flags(&args).unwrap_or_else(|message| {
+        eprintln!("{message}");
+        process::exit(2);
+    });
 
-    let score_part_1 = play(&input, parse_strategy_guide_part_1);
-    let score_part_2 = play(&input, parse_strategy_guide_part_2);
+    if args.has("--ciphers") {
+        print_ciphers(&args);
+        return;
+    }
+    if let Some(rounds) = args.value::<usize>("--tournament") {
+        print_tournament(&args, rounds, args.value("--seed").unwrap_or(0));
+        return;
+    }
 
-    println!("For part 1 your score would be: {score_part_1}");
-    println!("For part 2 your score would be: {score_part_2}");
+    solve_inputs::<Day02>(&args, |report| {
+        println!("For part 1 your score would be: {}", report.part_1);
+        println!("For part 2 your score would be: {}", report.part_2);
+    });
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum RPS {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
+fn check_flags(args: &Args) -> Result<(), String> {
+    args.at_most_one_of(&["--ciphers", "--tournament"])?;
+    args.only_with("--seed", &["--tournament"])
 }
 
-enum RPSResult {
-    Loss = 0,
-    Tie = 3,
-    Win = 6,
-}
+fn print_ciphers(args: &Args) {
+    for_each_input(args, |source, input| {
+        let rankings = rank_ciphers(input).map_err(|error| error.to_string())?;
+        let (best, worst) = (rankings[0], rankings[rankings.len() - 1]);
 
-impl RPS {
-    fn vs(&self, other: &RPS) -> RPSResult {
-        match (self, other) {
-            (RPS::Rock, RPS::Paper) => RPSResult::Loss,
-            (RPS::Rock, RPS::Scissors) => RPSResult::Win,
-            (RPS::Paper, RPS::Rock) => RPSResult::Win,
-            (RPS::Paper, RPS::Scissors) => RPSResult::Loss,
-            (RPS::Scissors, RPS::Rock) => RPSResult::Loss,
-            (RPS::Scissors, RPS::Paper) => RPSResult::Win,
-            _ => RPSResult::Tie,
+        match args.format {
+            Format::Json => {
+                let ranking = |Ranking { cipher, score }: Ranking| json!({"cipher": cipher.to_string(), "score": score});
+                let rankings: Vec<_> = rankings.iter().copied().map(ranking).collect();
+                let report = json!({
+                    "day": 2,
+                    "input": source.to_string(),
+                    "best": ranking(best),
+                    "worst": ranking(worst),
+                    "rankings": rankings,
+                });
+                println!("{report}");
+            }
+            Format::Text => {
+                println!("The best reading is {} for {}", best.cipher, best.score);
+                println!("The worst reading is {} for {}", worst.cipher, worst.score);
+                for (rank, Ranking { cipher, score }) in rankings.iter().enumerate() {
+                    let part = match *cipher {
+                        Cipher::PART_1 => " (part 1)",
+                        Cipher::PART_2 => " (part 2)",
+                        _ => "",
+                    };
+                    let cipher = cipher.to_string();
+                    println!("{:>3}. {cipher:<28} {score}{part}", rank + 1);
+                }
+            }
         }
-    }
+        Ok(())
+    });
 }
 
-fn parse_strategy_guide_part_1(input: &str) -> Vec<(RPS, RPS)> {
-    input
-        .trim()
-        .split('\n')
-        .map(|line| {
-            let round: Vec<_> = line
-                .split(' ')
-                .map(|char| match char {
-                    "A" | "X" => RPS::Rock,
-                    "B" | "Y" => RPS::Paper,
-                    "C" | "Z" => RPS::Scissors,
-                    _ => {
-                        panic!("invalid RPS character!");
-                    }
-                })
-                .collect();
-            (round[0], round[1])
-        })
-        .collect()
-}
+fn print_tournament(args: &Args, rounds: usize, seed: u64) {
+    for_each_input(args, |source, input| {
+        let guide = parse_strategy_guide_part_1(input).map_err(|error| error.to_string())?;
 
-fn parse_strategy_guide_part_2(input: &str) -> Vec<(RPS, RPS)> {
-    input
-        .trim()
-        .split('\n')
-        .map(|line| {
-            let round_input: Vec<_> = line.split(' ').collect();
-            let opponent = match round_input[0] {
-                "A" => RPS::Rock,
-                "B" => RPS::Paper,
-                "C" => RPS::Scissors,
-                _ => panic!("invalid RPS character!"),
-            };
-            let player = match (opponent, round_input[1]) {
-                (RPS::Rock, "X") => RPS::Scissors,
-                (RPS::Paper, "X") => RPS::Rock,
-                (RPS::Scissors, "X") => RPS::Paper,
-                (RPS::Rock, "Z") => RPS::Paper,
-                (RPS::Paper, "Z") => RPS::Scissors,
-                (RPS::Scissors, "Z") => RPS::Rock,
-                (any, "Y") => any,
-                _ => panic!("unexpected pattern"),
-            };
-            (opponent, player)
-        })
-        .collect()
-}
+        let mut players: Vec<Box<dyn Strategy>> = vec![
+            Box::new(FixedGuide::new(
+                "guide",
+                guide.iter().map(|r| r.1).collect(),
+            )),
+            Box::new(FixedGuide::new(
+                "opponent",
+                guide.iter().map(|r| r.0).collect(),
+            )),
+            Box::new(Random::new(seed)),
+            Box::new(FrequencyCounter),
+            Box::new(CopyLastMove),
+        ];
+        let table = round_robin(&mut players, rounds);
 
-fn play(strategy_guide: &str, parse: for<'r> fn(&'r str) -> Vec<(RPS, RPS)>) -> usize {
-    let mut score = 0;
-    for (opponent, player) in parse(strategy_guide) {
-        score += player.vs(&opponent) as usize + player as usize;
-    }
-    score
+        match args.format {
+            Format::Json => {
+                let table: Vec<_> = table
+                    .iter()
+                    .map(|standing| {
+                        let Standing {
+                            name,
+                            played,
+                            won,
+                            drawn,
+                            lost,
+                            points,
+                        } = standing;
+                        json!({"player": name, "played": played, "won": won, "drawn": drawn, "lost": lost, "points": points})
+                    })
+                    .collect();
+                let report = json!({"day": 2, "input": source.to_string(), "rounds": rounds, "table": table});
+                println!("{report}");
+            }
+            Format::Text => println!("{}", LeagueTable(&table)),
+        }
+        Ok(())
+    });
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn part_1() {
-        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
-        assert_eq!(play(&input, parse_strategy_guide_part_1), 15);
+    fn check_flags_of(args: &[&str]) -> Result<(), String> {
+        let args = Args::parse_with(args.iter().map(|arg| arg.to_string()), &FLAGS).unwrap();
+        check_flags(&args)
     }
 
     #[test]
-    fn part_2() {
-        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
-        assert_eq!(play(&input, parse_strategy_guide_part_2), 12);
+    fn test_check_flags() {
+        assert_eq!(check_flags_of(&["input.txt"]), Ok(()));
+        assert_eq!(check_flags_of(&["input.txt", "--ciphers"]), Ok(()));
+        assert_eq!(
+            check_flags_of(&["input.txt", "--tournament", "100", "--seed", "7"]),
+            Ok(())
+        );
+
+        assert!(check_flags_of(&["input.txt", "--ciphers", "--tournament", "100"]).is_err());
+        assert!(check_flags_of(&["input.txt", "--seed", "7"]).is_err());
+        assert!(check_flags_of(&["input.txt", "--ciphers", "--seed", "7"]).is_err());
     }
 }
@@ -0,0 +1,286 @@
+use crate::{score, RPSResult, RPS};
+use aoc_common::Rng;
+use std::cmp::{Ordering, Reverse};
+use std::fmt::{self, Display};
+
+const SHAPES: [RPS; 3] = [RPS::Rock, RPS::Paper, RPS::Scissors];
+
+// A player in a tournament. Each round it picks a shape, knowing how every
+// earlier round of the match went as (its shape, the opponent's shape).
+pub trait Strategy {
+    fn name(&self) -> &str;
+    fn choose(&mut self, history: &[(RPS, RPS)]) -> RPS;
+}
+
+// Plays the shapes it was given in order, starting again when it runs out.
+pub struct FixedGuide {
+    name: String,
+    shapes: Vec<RPS>,
+}
+
+impl FixedGuide {
+    pub fn new(name: &str, shapes: Vec<RPS>) -> Self {
+        assert!(!shapes.is_empty(), "a guide needs at least one shape");
+        Self {
+            name: name.to_string(),
+            shapes,
+        }
+    }
+}
+
+impl Strategy for FixedGuide {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn choose(&mut self, history: &[(RPS, RPS)]) -> RPS {
+        self.shapes[history.len() % self.shapes.len()]
+    }
+}
+
+// Picks any shape, the same ones every time for a seed.
+pub struct Random {
+    rng: Rng,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: Rng::new(seed),
+        }
+    }
+}
+
+impl Strategy for Random {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn choose(&mut self, _: &[(RPS, RPS)]) -> RPS {
+        *self.rng.choose(&SHAPES)
+    }
+}
+
+// Plays whatever beats the shape the opponent has played most so far (the
+// earliest of rock, paper and scissors on a tie), and rock to begin with.
+pub struct FrequencyCounter;
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> &str {
+        "frequency counter"
+    }
+
+    fn choose(&mut self, history: &[(RPS, RPS)]) -> RPS {
+        if history.is_empty() {
+            return RPS::Rock;
+        }
+        let mut counts = [0; 3];
+        for (_, opponent) in history {
+            counts[*opponent as usize - 1] += 1;
+        }
+        let most = SHAPES
+            .into_iter()
+            .rev()
+            .max_by_key(|shape| counts[*shape as usize - 1])
+            .expect("there are three shapes");
+        beats(most)
+    }
+}
+
+// Plays whatever the opponent played last round, and rock to begin with.
+pub struct CopyLastMove;
+
+impl Strategy for CopyLastMove {
+    fn name(&self) -> &str {
+        "copycat"
+    }
+
+    fn choose(&mut self, history: &[(RPS, RPS)]) -> RPS {
+        history.last().map_or(RPS::Rock, |&(_, opponent)| opponent)
+    }
+}
+
+// the shape that wins against `shape`
+fn beats(shape: RPS) -> RPS {
+    SHAPES
+        .into_iter()
+        .find(|winner| matches!(winner.vs(&shape), RPSResult::Win))
+        .expect("every shape is beaten by another")
+}
+
+// A player's line in the league table. Matches are won by scoring more
+// points over their rounds, which are scored as in the puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub played: usize,
+    pub won: usize,
+    pub drawn: usize,
+    pub lost: usize,
+    pub points: usize,
+}
+
+// Plays every player against every other for `rounds` rounds, returning the
+// league table from the most points to the fewest (and in the order the
+// players were given on a tie).
+pub fn round_robin(players: &mut [Box<dyn Strategy>], rounds: usize) -> Vec<Standing> {
+    let mut table: Vec<Standing> = players
+        .iter()
+        .map(|player| Standing {
+            name: player.name().to_string(),
+            played: 0,
+            won: 0,
+            drawn: 0,
+            lost: 0,
+            points: 0,
+        })
+        .collect();
+
+    for i in 0..players.len() {
+        for j in i + 1..players.len() {
+            let (left, right) = players.split_at_mut(j);
+            let [home, away] = play_match(&mut *left[i], &mut *right[0], rounds);
+
+            for (standing, ours, theirs) in [(i, home, away), (j, away, home)] {
+                let standing = &mut table[standing];
+                standing.played += 1;
+                standing.points += ours;
+                match ours.cmp(&theirs) {
+                    Ordering::Greater => standing.won += 1,
+                    Ordering::Equal => standing.drawn += 1,
+                    Ordering::Less => standing.lost += 1,
+                }
+            }
+        }
+    }
+
+    table.sort_by_key(|standing| Reverse(standing.points));
+    table
+}
+
+// each player's points from a match
+pub fn play_match(home: &mut dyn Strategy, away: &mut dyn Strategy, rounds: usize) -> [usize; 2] {
+    // each player sees the rounds from its own side
+    let mut home_history = Vec::with_capacity(rounds);
+    let mut away_history = Vec::with_capacity(rounds);
+
+    for _ in 0..rounds {
+        let home_shape = home.choose(&home_history);
+        let away_shape = away.choose(&away_history);
+        home_history.push((home_shape, away_shape));
+        away_history.push((away_shape, home_shape));
+    }
+
+    // score takes rounds as (opponent, player)
+    let points = |history: &[(RPS, RPS)]| {
+        let rounds: Vec<_> = history
+            .iter()
+            .map(|&(ours, theirs)| (theirs, ours))
+            .collect();
+        score(&rounds)
+    };
+    [points(&home_history), points(&away_history)]
+}
+
+pub struct LeagueTable<'a>(pub &'a [Standing]);
+
+impl Display for LeagueTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self.0.iter().map(|s| s.name.len()).max().unwrap_or(0);
+        write!(f, "    {:width$}  P  W  D  L  Points", "Player")?;
+        for (rank, standing) in self.0.iter().enumerate() {
+            let Standing {
+                name,
+                played,
+                won,
+                drawn,
+                lost,
+                points,
+            } = standing;
+            write!(
+                f,
+                "\n{:>2}. {name:width$} {played:>2} {won:>2} {drawn:>2} {lost:>2}  {points:>6}",
+                rank + 1
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_strategy_guide_part_1;
+    use std::fs;
+
+    #[test]
+    fn test_strategies() {
+        let history = [
+            (RPS::Rock, RPS::Paper),
+            (RPS::Rock, RPS::Scissors),
+            (RPS::Rock, RPS::Paper),
+        ];
+        assert_eq!(FrequencyCounter.choose(&history), RPS::Scissors);
+        assert_eq!(FrequencyCounter.choose(&history[..2]), RPS::Scissors);
+        assert_eq!(FrequencyCounter.choose(&history[1..2]), RPS::Rock);
+        assert_eq!(CopyLastMove.choose(&history), RPS::Paper);
+        assert_eq!(CopyLastMove.choose(&[]), RPS::Rock);
+
+        let mut guide = FixedGuide::new("guide", vec![RPS::Paper, RPS::Rock]);
+        assert_eq!(guide.choose(&history), RPS::Rock);
+
+        let shapes = |seed| {
+            let mut random = Random::new(seed);
+            (0..20).map(|_| random.choose(&[])).collect::<Vec<_>>()
+        };
+        assert_eq!(shapes(7), shapes(7));
+    }
+
+    #[test]
+    fn test_play_match() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let rounds = parse_strategy_guide_part_1(&input).unwrap();
+        let mut opponent = FixedGuide::new("opponent", rounds.iter().map(|r| r.0).collect());
+        let mut guide = FixedGuide::new("guide", rounds.iter().map(|r| r.1).collect());
+
+        // the guide scores what part 1 says it does
+        let [guide_points, _] = play_match(&mut guide, &mut opponent, rounds.len());
+        assert_eq!(guide_points, 15);
+
+        // copying a player that always plays rock ties every round, since the
+        // copycat opens with rock too
+        let mut rock = FixedGuide::new("rock", vec![RPS::Rock]);
+        assert_eq!(
+            play_match(&mut rock, &mut CopyLastMove, 10),
+            [10 * (3 + 1), 10 * (3 + 1)]
+        );
+        // and counting it loses every round after the first, scoring just the
+        // one point for rock
+        assert_eq!(
+            play_match(&mut rock, &mut FrequencyCounter, 10),
+            [(3 + 1) + 9, (3 + 1) + 9 * (6 + 2)]
+        );
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let mut players: Vec<Box<dyn Strategy>> = vec![
+            Box::new(FixedGuide::new("rock", vec![RPS::Rock])),
+            Box::new(FrequencyCounter),
+            Box::new(CopyLastMove),
+            Box::new(Random::new(0)),
+        ];
+        let table = round_robin(&mut players, 100);
+
+        assert_eq!(table.len(), 4);
+        assert!(table.iter().all(|standing| standing.played == 3));
+        assert!(table.windows(2).all(|w| w[0].points >= w[1].points));
+        let results: usize = table.iter().map(|s| s.won + s.lost).sum();
+        let draws: usize = table.iter().map(|s| s.drawn).sum();
+        assert_eq!(results + draws, 2 * 6);
+        assert_eq!(table.last().unwrap().name, "rock");
+
+        let rendered = LeagueTable(&table).to_string();
+        assert_eq!(rendered.lines().count(), 5);
+    }
+}
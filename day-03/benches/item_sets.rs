@@ -0,0 +1,66 @@
+// Compares finding the common item types with bitmasks (as the solution
+// does) against collecting each rucksack into a HashSet, on a big generated
+// input.
+use aoc_common::Rng;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day_03::generator::generate;
+use day_03::{parse_rucksacks, part_1, part_2, priority, GROUP_SIZE};
+use std::collections::HashSet;
+
+fn part_1_hash_sets(lines: &[&str]) -> usize {
+    let mut total = 0;
+    for line in lines {
+        let (first, second) = line.split_at(line.len() / 2);
+        let first_set: HashSet<char> = first.chars().collect();
+        let second_set: HashSet<char> = second.chars().collect();
+        let common_type = first_set
+            .intersection(&second_set)
+            .next()
+            .expect("we expect one common type between compartments");
+        total += priority(*common_type);
+    }
+    total
+}
+
+fn part_2_hash_sets(lines: &[&str]) -> usize {
+    let mut total = 0;
+    for group in lines.chunks_exact(3) {
+        let mut first: HashSet<char> = group[0].chars().collect();
+        let second: HashSet<char> = group[1].chars().collect();
+        let third: HashSet<char> = group[2].chars().collect();
+        first.retain(|t| second.contains(t));
+        first.retain(|t| third.contains(t));
+        let common_type = first
+            .iter()
+            .next()
+            .expect("we expect one common type per group");
+        total += priority(*common_type);
+    }
+    total
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let input = generate(&mut Rng::new(0), 100_000);
+    let lines = parse_rucksacks(&input).expect("failed to parse input");
+    assert_eq!(part_1(&lines), part_1_hash_sets(&lines));
+    assert_eq!(part_2(&lines, GROUP_SIZE), part_2_hash_sets(&lines));
+
+    let mut group = c.benchmark_group("part_1");
+    group.bench_function("bitmask", |b| b.iter(|| part_1(black_box(&lines))));
+    group.bench_function("hash_set", |b| {
+        b.iter(|| part_1_hash_sets(black_box(&lines)))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("part_2");
+    group.bench_function("bitmask", |b| {
+        b.iter(|| part_2(black_box(&lines), GROUP_SIZE))
+    });
+    group.bench_function("hash_set", |b| {
+        b.iter(|| part_2_hash_sets(black_box(&lines)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
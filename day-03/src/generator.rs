@@ -0,0 +1,56 @@
+// Generates inputs in the puzzle's format: one rucksack of item types per
+// line, where each rucksack's compartments share exactly one type and each
+// group of three rucksacks shares exactly one badge. `size` is the number of
+// groups.
+use aoc_common::Rng;
+
+const ITEM_TYPES: &[u8; 52] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+
+    for _ in 0..size.max(1) {
+        // the badge, then a separate pool of 17 types for each elf in the
+        // group, so that the badge is the only type all three have
+        let mut types = ITEM_TYPES.to_vec();
+        rng.shuffle(&mut types);
+        let (badge, pools) = types.split_first().unwrap();
+
+        for pool in pools.chunks_exact(17) {
+            // one type in both compartments, and separate fillers for each
+            let (shared, fillers) = pool.split_first().unwrap();
+            let (first_fillers, second_fillers) = fillers.split_at(8);
+            let len = rng.range(4..=16);
+
+            let mut first = vec![*shared, *badge];
+            first.extend((2..len).map(|_| *rng.choose(first_fillers)));
+            let mut second = vec![*shared];
+            second.extend((1..len).map(|_| *rng.choose(second_fillers)));
+            rng.shuffle(&mut first);
+            rng.shuffle(&mut second);
+
+            output.extend(first.iter().chain(&second).map(|&b| b as char));
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day03, GROUP_SIZE};
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 20);
+            let rucksacks = Day03::parse(&input).unwrap();
+            assert_eq!(rucksacks.len(), 60);
+            part_1(&rucksacks);
+            part_2(&rucksacks, GROUP_SIZE);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::ops::{BitAnd, BitOr};
+
+// A set of item types, as a bitmask with the bit for each type's priority
+// set, so that finding the types rucksacks share is a few ANDs rather than
+// hashing every item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ItemSet(pub u64);
+
+impl ItemSet {
+    pub const EMPTY: Self = Self(0);
+
+    // every type there is, a-z and A-Z
+    pub const ALL: Self = Self(((1 << 52) - 1) << 1);
+
+    // the types in a line of items, which must all be a-z or A-Z
+    pub fn from_items(items: &str) -> Self {
+        Self(
+            items
+                .bytes()
+                .fold(0, |set, item| set | 1 << item_priority(item)),
+        )
+    }
+
+    // the types every one of the sets has
+    pub fn intersect_all(sets: impl IntoIterator<Item = Self>) -> Self {
+        sets.into_iter().fold(Self::ALL, BitAnd::bitand)
+    }
+
+    pub fn contains(self, priority: usize) -> bool {
+        self.0 & 1 << priority != 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    // the lowest priority in the set
+    pub fn first(self) -> Option<usize> {
+        (!self.is_empty()).then(|| self.0.trailing_zeros() as usize)
+    }
+
+    // the priorities in the set, lowest first
+    pub fn priorities(self) -> impl Iterator<Item = usize> {
+        let mut set = self.0;
+        std::iter::from_fn(move || {
+            let priority = (set != 0).then(|| set.trailing_zeros() as usize)?;
+            set &= set - 1;
+            Some(priority)
+        })
+    }
+}
+
+// Like `priority`, without the branches: the low five bits of a letter are
+// its place in the alphabet, and bit 5 is set for lower case.
+fn item_priority(item: u8) -> u32 {
+    let upper = (!item >> 5 & 1) as u32;
+    (item & 0x1f) as u32 + 26 * upper
+}
+
+impl BitAnd for ItemSet {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::priority;
+
+    #[test]
+    fn test_item_sets() {
+        for item in ('a'..='z').chain('A'..='Z') {
+            let set = ItemSet::from_items(&item.to_string());
+            assert_eq!(set.first(), Some(priority(item)));
+        }
+
+        let first = ItemSet::from_items("vJrwpWtwJgWr");
+        let second = ItemSet::from_items("hcsFMMfFFhFp");
+        assert_eq!((first & second).priorities().collect::<Vec<_>>(), [16]);
+        assert_eq!(first.len(), 8);
+        assert!(first.contains(priority('J')));
+        assert_eq!((first | second).len(), 8 + 7 - 1);
+
+        let group = [
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+        ];
+        let badge = ItemSet::intersect_all(group.map(ItemSet::from_items));
+        assert_eq!(badge.priorities().collect::<Vec<_>>(), [priority('r')]);
+
+        assert_eq!(ItemSet::intersect_all([]), ItemSet::ALL);
+        assert_eq!(ItemSet::ALL.len(), 52);
+        assert_eq!(ItemSet::EMPTY.first(), None);
+    }
+}
@@ -0,0 +1,261 @@
+pub mod generator;
+pub mod items;
+pub mod repack;
+
+use aoc_common::{ParseError, Result, Solution};
+use items::ItemSet;
+
+// one rucksack per line, with two equally sized compartments of item types a-z and A-Z
+pub fn parse_rucksacks(input: &str) -> Result<Vec<&str>> {
+    input
+        .trim()
+        .split('\n')
+        .map(|line| {
+            if let Some((i, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+                let at = &line[i..i + c.len_utf8()];
+                let message = format!("invalid item type {c:?}");
+                return Err(ParseError::new(Day03::DAY, input, at, message));
+            }
+            if line.len() % 2 != 0 {
+                let message = "a rucksack needs an even number of items to fill two compartments";
+                return Err(ParseError::new(Day03::DAY, input, line, message));
+            }
+            Ok(line)
+        })
+        .collect()
+}
+
+// the number of rucksacks in each group of elves in the puzzle
+pub const GROUP_SIZE: usize = 3;
+
+// the item types in both compartments of the rucksack
+pub fn misplaced(rucksack: &str) -> ItemSet {
+    let (first, second) = rucksack.split_at(rucksack.len() / 2);
+    ItemSet::from_items(first) & ItemSet::from_items(second)
+}
+
+// The item types every rucksack in a group carries, for each group of
+// `group_size` rucksacks in turn. A last group with too few rucksacks is
+// left out.
+pub fn badges<'a>(rucksacks: &'a [&str], group_size: usize) -> impl Iterator<Item = ItemSet> + 'a {
+    assert!(group_size > 0, "groups need at least one rucksack");
+    rucksacks.chunks_exact(group_size).map(|group| {
+        ItemSet::intersect_all(group.iter().map(|rucksack| ItemSet::from_items(rucksack)))
+    })
+}
+
+// the priorities of every misplaced item type
+pub fn part_1(lines: &[&str]) -> usize {
+    lines
+        .iter()
+        .map(|line| misplaced(line).priorities().sum::<usize>())
+        .sum()
+}
+
+// the priorities of every group's badges
+pub fn part_2(lines: &[&str], group_size: usize) -> usize {
+    badges(lines, group_size)
+        .map(|badges| badges.priorities().sum::<usize>())
+        .sum()
+}
+
+// Everything wrong with the rucksacks as far as the puzzle is concerned:
+// each rucksack should have exactly one item type in both compartments, and
+// each group of `group_size` exactly one badge, with no rucksacks left over.
+pub fn diagnose(input: &str, rucksacks: &[&str], group_size: usize) -> Vec<ParseError> {
+    let mut problems = vec![];
+    let mut check = |shared: ItemSet, at: &str, what: &str| {
+        let message = match shared.len() {
+            1 => return,
+            0 => format!("{what} no item type"),
+            n => format!("{what} {n} item types: {}", describe(shared)),
+        };
+        problems.push(ParseError::new(Day03::DAY, input, at, message));
+    };
+
+    for rucksack in rucksacks {
+        check(misplaced(rucksack), rucksack, "the compartments share");
+    }
+
+    let what = format!("this group of {group_size} rucksacks shares");
+    for (group, badges) in rucksacks
+        .chunks(group_size)
+        .zip(badges(rucksacks, group_size))
+    {
+        check(badges, group[0], &what);
+    }
+
+    let left_over = rucksacks.len() % group_size;
+    if left_over != 0 {
+        let first = rucksacks[rucksacks.len() - left_over];
+        let message = format!("the last group has {left_over} rucksacks, not {group_size}");
+        problems.push(ParseError::new(Day03::DAY, input, first, message));
+    }
+
+    problems.sort_by_key(|problem| problem.line);
+    problems
+}
+
+// like "a (1), B (28)"
+pub fn describe(items: ItemSet) -> String {
+    let items: Vec<String> = items
+        .priorities()
+        .map(|priority| format!("{} ({priority})", item_type(priority)))
+        .collect();
+    items.join(", ")
+}
+
+pub fn priority(type_char: char) -> usize {
+    (match type_char as u8 {
+        lower if lower.is_ascii_lowercase() => lower - b'a' + 1,
+        upper if upper.is_ascii_uppercase() => upper - b'A' + 27,
+        _ => panic!("unexpected type char"),
+    }) as usize
+}
+
+// the item type with the priority, from 1 to 52
+pub fn item_type(priority: usize) -> char {
+    b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ"[priority - 1] as char
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: usize = 3;
+
+    type Input<'a> = Vec<&'a str>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    // rucksacks that don't have exactly one misplaced type or badge are
+    // errors, so that the parts only ever find one of each
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let rucksacks = parse_rucksacks(input)?;
+        match diagnose(input, &rucksacks, GROUP_SIZE).into_iter().next() {
+            Some(problem) => Err(problem),
+            None => Ok(rucksacks),
+        }
+    }
+
+    fn part_1(lines: &Vec<&str>) -> usize {
+        part_1(lines)
+    }
+
+    fn part_2(lines: &Vec<&str>) -> usize {
+        part_2(lines, GROUP_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_priority() {
+        let types_priorities = vec![
+            ('p', 16),
+            ('L', 38),
+            ('P', 42),
+            ('v', 22),
+            ('t', 20),
+            ('s', 19),
+        ];
+
+        for (type_char, expected_priority) in types_priorities {
+            assert_eq!(priority(type_char), expected_priority);
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let lines: Vec<_> = input.trim().split('\n').collect();
+        assert_eq!(part_1(&lines), 157);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let lines: Vec<_> = input.trim().split('\n').collect();
+        assert_eq!(part_2(&lines, GROUP_SIZE), 70);
+    }
+
+    #[test]
+    fn test_parse_rucksacks() {
+        let error = parse_rucksacks("vJrwpWtwJgWr\nabc-ef").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+
+        let error = parse_rucksacks("vJrwpWtwJgWr\nabcde").unwrap_err();
+        assert_eq!((error.line, error.snippet.as_str()), (2, "abcde"));
+    }
+
+    #[test]
+    fn test_group_sizes() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let lines = parse_rucksacks(&input).unwrap();
+
+        // the whole list shares nothing, and pairs share several types
+        assert_eq!(badges(&lines, 6).collect::<Vec<_>>(), [ItemSet::EMPTY]);
+        let pairs: Vec<String> = badges(&lines, 2).map(describe).collect();
+        assert_eq!(
+            pairs,
+            [
+                "f (6), r (18), s (19), F (32), M (39)",
+                "q (17), v (22), w (23), B (28), T (46)",
+                "G (33), J (36), Z (52)",
+            ]
+        );
+        assert_eq!(part_2(&lines, 2), 114 + 136 + 121);
+        assert_eq!(part_2(&lines, 1), part_1_both_compartments(&lines));
+    }
+
+    // a rucksack on its own shares every type it holds with itself
+    fn part_1_both_compartments(lines: &[&str]) -> usize {
+        lines
+            .iter()
+            .map(|line| ItemSet::from_items(line).priorities().sum::<usize>())
+            .sum()
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let lines = parse_rucksacks(&input).unwrap();
+        assert!(diagnose(&input, &lines, GROUP_SIZE).is_empty());
+
+        let problems = diagnose(&input, &lines, 4);
+        let messages: Vec<_> = problems
+            .iter()
+            .map(|p| (p.line, p.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                (1, "this group of 4 rucksacks shares no item type"),
+                (5, "the last group has 2 rucksacks, not 4"),
+            ]
+        );
+
+        let input = "abcabc\nabcd\naxyz";
+        let problems = diagnose(input, &parse_rucksacks(input).unwrap(), GROUP_SIZE);
+        let messages: Vec<_> = problems
+            .iter()
+            .map(|p| (p.line, p.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                (
+                    1,
+                    "the compartments share 3 item types: a (1), b (2), c (3)"
+                ),
+                (2, "the compartments share no item type"),
+                (3, "the compartments share no item type"),
+            ]
+        );
+
+        let error = Day03::parse(input).unwrap_err();
+        assert_eq!(error.message, messages[0].1);
+    }
+}
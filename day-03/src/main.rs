@@ -1,96 +1,227 @@
 // Advent of Code 2022: Day 3
 // https://adventofcode.com/2022/day/3
-// Usage: `cargo run <input-file>`
-
-use std::collections::HashSet;
-use std::env;
-use std::fs;
+// Usage: `cargo run <input-file|directory|->... [--format text|json] [--shared | --repack [--between-rucksacks]] [--group-size N]`
+//
+// With `--shared` every item type each rucksack has in both compartments, and
+// each group has in common, is listed with its priority, along with anything
+// that doesn't fit the puzzle (such as a group with no badge), rather than
+// just the answers. `--repack` instead moves items between compartments so
+// that no rucksack has a type in both, printing the repacked rucksacks and
+// how much priority that saves; with `--between-rucksacks` a rucksack that
+// can't be fixed on its own may swap an item with another in its group.
+// Groups are of three rucksacks unless `--group-size` says otherwise, which
+// only those two modes take, since the answers are always for groups of three.
+
+use aoc_common::{for_each_input, solve_inputs, Args, Format};
+use day_03::items::ItemSet;
+use day_03::repack::{plan, Exchange};
+use day_03::{
+    badges, describe, diagnose, item_type, misplaced, parse_rucksacks, part_1, part_2, Day03,
+    GROUP_SIZE,
+};
+use serde_json::json;
+use std::process;
+
+const FLAGS: [&str; 4] = [
+    "--shared",
+    "--repack",
+    "--between-rucksacks",
+    "--group-size N",
+];
 
 fn main() {
-    let input_filename = env::args().nth(1).expect("please supply an input filename");
-    let input = fs::read_to_string(&input_filename).expect("failed to read input file");
-    let lines: Vec<_> = input.trim().split('\n').collect();
+    let args = Args::from_env_with(&FLAGS);
+    let group_size = group_size(&args).unwrap_or_else(|message| {
+        eprintln!("{message}");
+        process::exit(2);
+    });
+
+    if args.has("--shared") {
+        print_shared(&args, group_size);
+        return;
+    }
+    if args.has("--repack") {
+        let group_size = args.has("--between-rucksacks").then_some(group_size);
+        print_repacked(&args, group_size);
+        return;
+    }
 
-    println!("The answer for part 1 is {}", part_1(&lines));
-    println!("The answer for part 2 is {}", part_2(&lines));
+    solve_inputs::<Day03>(&args, |report| {
+        println!("The answer for part 1 is {}", report.part_1);
+        println!("The answer for part 2 is {}", report.part_2);
+    });
 }
 
-fn part_1(lines: &[&str]) -> usize {
-    let mut total = 0;
-    for line in lines {
-        let (first, second) = line.split_at(line.len() / 2);
-        let first_set: HashSet<char> = first.chars().collect();
-        let second_set: HashSet<char> = second.chars().collect();
-        let common_type = first_set
-            .intersection(&second_set)
-            .next()
-            .expect("we expect one common type between compartments");
-        total += priority(*common_type);
+// The size of groups, which can only be changed for the modes that use it,
+// once the flags are known to make sense together.
+fn group_size(args: &Args) -> Result<usize, String> {
+    args.at_most_one_of(&["--shared", "--repack"])?;
+    args.only_with("--between-rucksacks", &["--repack"])?;
+
+    let Some(group_size) = args.value("--group-size") else {
+        return Ok(GROUP_SIZE);
+    };
+    let grouped = args.has("--shared") || args.has("--repack") && args.has("--between-rucksacks");
+    if !grouped {
+        return Err(String::from(
+            "--group-size only applies with --shared or --repack --between-rucksacks",
+        ));
     }
-    total
+    if group_size == 0 {
+        return Err(String::from("--group-size needs to be at least 1"));
+    }
+    Ok(group_size)
 }
 
-fn part_2(lines: &[&str]) -> usize {
-    let mut total = 0;
-
-    for group in lines.chunks_exact(3) {
-        let mut first: HashSet<char> = group[0].chars().collect();
-        let second: HashSet<char> = group[1].chars().collect();
-        let third: HashSet<char> = group[2].chars().collect();
-
-        first.retain(|t| second.contains(t));
-        first.retain(|t| third.contains(t));
-
-        let common_type = first
-            .iter()
-            .next()
-            .expect("we expect one common type per group");
-
-        total += priority(*common_type);
-    }
+fn print_shared(args: &Args, group_size: usize) {
+    for_each_input(args, |source, input| {
+        let rucksacks = parse_rucksacks(input).map_err(|error| error.to_string())?;
+        let problems = diagnose(input, &rucksacks, group_size);
+
+        let misplaced: Vec<ItemSet> = rucksacks.iter().map(|r| misplaced(r)).collect();
+        let badges: Vec<ItemSet> = badges(&rucksacks, group_size).collect();
+        let (part_1, part_2) = (part_1(&rucksacks), part_2(&rucksacks, group_size));
+
+        match args.format {
+            Format::Json => {
+                let items = |items: &ItemSet| -> Vec<_> {
+                    items
+                        .priorities()
+                        .map(|p| json!({"item": item_type(p).to_string(), "priority": p}))
+                        .collect()
+                };
+                let report = json!({
+                    "day": 3,
+                    "input": source.to_string(),
+                    "group_size": group_size,
+                    "rucksacks": misplaced.iter().map(items).collect::<Vec<_>>(),
+                    "groups": badges.iter().map(items).collect::<Vec<_>>(),
+                    "part_1": part_1,
+                    "part_2": part_2,
+                    "problems": problems.iter().map(|p| json!({"line": p.line, "message": p.message})).collect::<Vec<_>>(),
+                });
+                println!("{report}");
+            }
+            Format::Text => {
+                let describe = |items: ItemSet| match items.len() {
+                    0 => String::from("nothing"),
+                    _ => describe(items),
+                };
+                for (i, items) in misplaced.into_iter().enumerate() {
+                    println!("Rucksack {}: {}", i + 1, describe(items));
+                }
+                for (i, items) in badges.into_iter().enumerate() {
+                    let first = i * group_size + 1;
+                    let last = first + group_size - 1;
+                    println!(
+                        "Group {} (rucksacks {first}-{last}): {}",
+                        i + 1,
+                        describe(items)
+                    );
+                }
+                println!("Misplaced priorities: {part_1}");
+                println!("Badge priorities: {part_2}");
+            }
+        }
 
-    total
+        // what doesn't fit the puzzle is still listed above, but fails the input
+        match problems.is_empty() {
+            true => Ok(()),
+            false => {
+                let problems: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+                Err(problems.join("\n"))
+            }
+        }
+    });
 }
 
-fn priority(type_char: char) -> usize {
-    (match type_char as u8 {
-        lower if (b'a'..=b'z').contains(&lower) => lower - b'a' + 1,
-        upper if (b'A'..=b'Z').contains(&upper) => upper - b'A' + 27,
-        _ => panic!("unexpected type char"),
-    }) as usize
+fn print_repacked(args: &Args, group_size: Option<usize>) {
+    for_each_input(args, |source, input| {
+        let rucksacks = parse_rucksacks(input).map_err(|error| error.to_string())?;
+        let plan = plan(&rucksacks, group_size);
+
+        match args.format {
+            Format::Json => {
+                let exchanges: Vec<_> = plan
+                    .exchanges
+                    .iter()
+                    .map(|Exchange { from, to, gave, took }| {
+                        json!({"from": from + 1, "to": to + 1, "gave": gave.to_string(), "took": took.to_string()})
+                    })
+                    .collect();
+                let report = json!({
+                    "day": 3,
+                    "input": source.to_string(),
+                    "rucksacks": plan.rucksacks,
+                    "moves": plan.moves,
+                    "exchanges": exchanges,
+                    "unfixable": plan.unfixable.iter().map(|i| i + 1).collect::<Vec<_>>(),
+                    "saved": plan.saved,
+                });
+                println!("{report}");
+            }
+            Format::Text => {
+                // the rucksacks go to stdout in the input's format, so that
+                // they can be saved and solved again
+                for rucksack in &plan.rucksacks {
+                    println!("{rucksack}");
+                }
+                for Exchange {
+                    from,
+                    to,
+                    gave,
+                    took,
+                } in &plan.exchanges
+                {
+                    eprintln!(
+                        "Rucksack {} gave {gave} to rucksack {} for {took}",
+                        from + 1,
+                        to + 1
+                    );
+                }
+                eprintln!("Moved {} items, saving {} priority", plan.moves, plan.saved);
+                if !plan.unfixable.is_empty() {
+                    let unfixable: Vec<String> =
+                        plan.unfixable.iter().map(|i| (i + 1).to_string()).collect();
+                    eprintln!("Couldn't repack rucksacks {}", unfixable.join(", "));
+                }
+            }
+        }
+        Ok(())
+    });
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_priority() {
-        let types_priorities = vec![
-            ('p', 16),
-            ('L', 38),
-            ('P', 42),
-            ('v', 22),
-            ('t', 20),
-            ('s', 19),
-        ];
-
-        for (type_char, expected_priority) in types_priorities {
-            assert_eq!(priority(type_char), expected_priority);
-        }
-    }
-
-    #[test]
-    fn test_part_1() {
-        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
-        let lines: Vec<_> = input.trim().split('\n').collect();
-        assert_eq!(part_1(&lines), 157);
+    fn group_size_of(args: &[&str]) -> Result<usize, String> {
+        let args = Args::parse_with(args.iter().map(|arg| arg.to_string()), &FLAGS).unwrap();
+        group_size(&args)
     }
 
     #[test]
-    fn test_part_2() {
-        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
-        let lines: Vec<_> = input.trim().split('\n').collect();
-        assert_eq!(part_2(&lines), 70);
+    fn test_group_size() {
+        assert_eq!(group_size_of(&["input.txt"]), Ok(GROUP_SIZE));
+        assert_eq!(group_size_of(&["input.txt", "--shared"]), Ok(GROUP_SIZE));
+        assert_eq!(
+            group_size_of(&["input.txt", "--shared", "--group-size", "4"]),
+            Ok(4)
+        );
+        assert_eq!(
+            group_size_of(&["-", "--repack", "--between-rucksacks", "--group-size", "2"]),
+            Ok(2)
+        );
+
+        // the answers are always for groups of three, so the size can't be
+        // changed without a mode that uses it
+        assert!(group_size_of(&["input.txt", "--group-size", "4"]).is_err());
+        assert!(group_size_of(&["input.txt", "--repack", "--group-size", "4"]).is_err());
+        assert!(group_size_of(&["input.txt", "--shared", "--group-size", "0"]).is_err());
+
+        // and the modes can't be mixed
+        assert!(group_size_of(&["input.txt", "--between-rucksacks"]).is_err());
+        assert!(group_size_of(&["input.txt", "--shared", "--between-rucksacks"]).is_err());
+        assert!(group_size_of(&["input.txt", "--shared", "--repack"]).is_err());
     }
 }
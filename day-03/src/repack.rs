@@ -0,0 +1,319 @@
+use crate::{misplaced, priority};
+
+// Swapping an item between two rucksacks of a group, for a rucksack that
+// can't be repacked on its own. Rucksacks are numbered from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Exchange {
+    pub from: usize,
+    pub to: usize,
+    pub gave: char,
+    pub took: char,
+}
+
+// How to repack a list of rucksacks so that no item type is in both of a
+// rucksack's compartments, keeping the compartments the same size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    // the rucksacks after repacking, in the same format as the input
+    pub rucksacks: Vec<String>,
+    // items moved from one compartment to the other or between rucksacks
+    pub moves: usize,
+    pub exchanges: Vec<Exchange>,
+    // the rucksacks that can't be repacked, which are left as they were
+    pub unfixable: Vec<usize>,
+    // how much lower the total priority of misplaced items is
+    pub saved: usize,
+}
+
+// The fewest moves between its compartments that leave no item type in
+// both, and the rucksack after making them. Every type has to end up
+// entirely in one compartment, so this picks the types for the first
+// compartment that exactly fill it while moving as few items as possible
+// (a knapsack over the types), or None if no choice of types fills it.
+pub fn repack(rucksack: &str) -> Option<(String, usize)> {
+    let half = rucksack.len() / 2;
+    let (first, second) = rucksack.split_at(half);
+
+    // how many of each type are in each compartment, by priority
+    let mut counts = [[0; 2]; 53];
+    for item in first.chars() {
+        counts[priority(item)][0] += 1;
+    }
+    for item in second.chars() {
+        counts[priority(item)][1] += 1;
+    }
+    let types: Vec<usize> = (1..=52).filter(|&t| counts[t] != [0, 0]).collect();
+
+    // fewest[i][size] is the fewest moves to put exactly `size` items of
+    // the first i types in the first compartment (and the rest of them in
+    // the second), and went_first[i][size] whether the i-th type went first to
+    // get there
+    let mut fewest = vec![vec![None; half + 1]; types.len() + 1];
+    let mut went_first = vec![vec![false; half + 1]; types.len() + 1];
+    fewest[0][0] = Some(0);
+    for (i, &t) in types.iter().enumerate() {
+        let [in_first, in_second] = counts[t];
+        for size in 0..=half {
+            let Some(moves) = fewest[i][size] else {
+                continue;
+            };
+            // keeping the type in the second compartment moves its items
+            // from the first, and in the first moves them from the second
+            let options = [
+                (size, moves + in_first, false),
+                (size + in_first + in_second, moves + in_second, true),
+            ];
+            for (size, moves, goes_first) in options {
+                if size <= half && fewest[i + 1][size].is_none_or(|fewest| moves < fewest) {
+                    fewest[i + 1][size] = Some(moves);
+                    went_first[i + 1][size] = goes_first;
+                }
+            }
+        }
+    }
+    let moves = fewest[types.len()][half]?;
+
+    // walk back through the table to find which types went first
+    let mut in_first = [false; 53];
+    let mut size = half;
+    for (i, &t) in types.iter().enumerate().rev() {
+        if went_first[i + 1][size] {
+            in_first[t] = true;
+            size -= counts[t][0] + counts[t][1];
+        }
+    }
+
+    let goes_first = |item: &char| in_first[priority(*item)];
+    let repacked: String = first
+        .chars()
+        .filter(goes_first)
+        .chain(second.chars().filter(goes_first))
+        .chain(second.chars().filter(|item| !goes_first(item)))
+        .chain(first.chars().filter(|item| !goes_first(item)))
+        .collect();
+    Some((repacked, moves))
+}
+
+// Repacks every rucksack on its own. With a group size, a rucksack that
+// can't be repacked on its own may swap one item with another rucksack in
+// its group first, picking the swap that needs the fewest moves in all.
+pub fn plan(rucksacks: &[&str], group_size: Option<usize>) -> Plan {
+    let mut current: Vec<String> = rucksacks.iter().map(|r| r.to_string()).collect();
+    let mut exchanges = vec![];
+
+    if let Some(group_size) = group_size {
+        assert!(group_size > 0, "groups need at least one rucksack");
+        for i in 0..current.len() {
+            if repack(&current[i]).is_some() {
+                continue;
+            }
+            let group =
+                i / group_size * group_size..((i / group_size + 1) * group_size).min(current.len());
+            if let Some((exchange, ours, theirs)) = best_exchange(&current, i, group) {
+                current[exchange.from] = ours;
+                current[exchange.to] = theirs;
+                exchanges.push(exchange);
+            }
+        }
+    }
+
+    let mut moves = 2 * exchanges.len();
+    let mut unfixable = vec![];
+    let mut repacked = vec![];
+    for (i, rucksack) in current.iter().enumerate() {
+        match repack(rucksack) {
+            Some((rucksack, n)) => {
+                repacked.push(rucksack);
+                moves += n;
+            }
+            None => {
+                unfixable.push(i);
+                repacked.push(rucksack.clone());
+            }
+        }
+    }
+
+    let misplaced_priority = |rucksack: &str| -> usize { misplaced(rucksack).priorities().sum() };
+    let before: usize = rucksacks.iter().map(|r| misplaced_priority(r)).sum();
+    let after: usize = repacked.iter().map(|r| misplaced_priority(r)).sum();
+
+    Plan {
+        rucksacks: repacked,
+        moves,
+        exchanges,
+        unfixable,
+        saved: before - after,
+    }
+}
+
+// The swap between rucksack `from` and another in the group that lets both
+// be repacked with the fewest moves, and the two rucksacks after the swap.
+fn best_exchange(
+    rucksacks: &[String],
+    from: usize,
+    group: std::ops::Range<usize>,
+) -> Option<(Exchange, String, String)> {
+    let mut best: Option<(usize, Exchange, String, String)> = None;
+    let ours = &rucksacks[from];
+
+    for to in group.filter(|&to| to != from) {
+        let theirs = &rucksacks[to];
+        for (i, gave) in first_of_each_type(ours) {
+            for (j, took) in first_of_each_type(theirs) {
+                if gave == took {
+                    continue;
+                }
+                let ours = replaced(ours, i, took);
+                let theirs = replaced(theirs, j, gave);
+                let (Some((_, our_moves)), Some((_, their_moves))) =
+                    (repack(&ours), repack(&theirs))
+                else {
+                    continue;
+                };
+                let moves = 2 + our_moves + their_moves;
+                if best.as_ref().is_none_or(|(fewest, ..)| moves < *fewest) {
+                    let exchange = Exchange {
+                        from,
+                        to,
+                        gave,
+                        took,
+                    };
+                    best = Some((moves, exchange, ours, theirs));
+                }
+            }
+        }
+    }
+
+    best.map(|(_, exchange, ours, theirs)| (exchange, ours, theirs))
+}
+
+// the position of the first item of each type in each compartment
+fn first_of_each_type(rucksack: &str) -> Vec<(usize, char)> {
+    let half = rucksack.len() / 2;
+    let mut seen = vec![];
+    let mut firsts = vec![];
+    for (i, item) in rucksack.char_indices() {
+        let key = (i < half, item);
+        if !seen.contains(&key) {
+            seen.push(key);
+            firsts.push((i, item));
+        }
+    }
+    firsts
+}
+
+fn replaced(rucksack: &str, i: usize, item: char) -> String {
+    let mut rucksack = rucksack.to_string();
+    rucksack.replace_range(i..i + 1, &item.to_string());
+    rucksack
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::ItemSet;
+    use crate::parse_rucksacks;
+    use aoc_common::Rng;
+    use std::fs;
+
+    // the rucksack holds the same items, split in half with no type in both
+    fn assert_repacked(before: &str, after: &str) {
+        let sorted = |rucksack: &str| {
+            let mut items: Vec<char> = rucksack.chars().collect();
+            items.sort_unstable();
+            items
+        };
+        assert_eq!(sorted(before), sorted(after), "{before} became {after}");
+        assert_eq!(
+            misplaced(after),
+            ItemSet::EMPTY,
+            "{after} still has misplaced items"
+        );
+    }
+
+    // the fewest moves found by trying every way of splitting the types
+    fn fewest_moves(rucksack: &str) -> Option<usize> {
+        let (first, second) = rucksack.split_at(rucksack.len() / 2);
+        let mut types: Vec<char> = rucksack.chars().collect();
+        types.sort_unstable();
+        types.dedup();
+
+        (0..1 << types.len())
+            .filter_map(|chosen: usize| {
+                let goes_first =
+                    |item: char| chosen >> types.iter().position(|&t| t == item).unwrap() & 1 == 1;
+                let size = rucksack.chars().filter(|&item| goes_first(item)).count();
+                let moves = first.chars().filter(|&item| !goes_first(item)).count()
+                    + second.chars().filter(|&item| goes_first(item)).count();
+                (size == first.len()).then_some(moves)
+            })
+            .min()
+    }
+
+    #[test]
+    fn test_repack() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        for rucksack in parse_rucksacks(&input).unwrap() {
+            let (repacked, _) = repack(rucksack).unwrap();
+            assert_repacked(rucksack, &repacked);
+        }
+
+        assert_eq!(repack("abcd"), Some((String::from("abcd"), 0)));
+        assert_eq!(repack("aabcba"), Some((String::from("aaacbb"), 2)));
+        assert_eq!(repack("aaab"), None);
+    }
+
+    #[test]
+    fn test_repack_moves_fewest() {
+        let mut rng = Rng::new(3);
+        for _ in 0..500 {
+            let len = 2 * rng.range(1..=6);
+            let rucksack: String = (0..len)
+                .map(|_| *rng.choose(&['a', 'b', 'c', 'D', 'E']))
+                .collect();
+            let repacked = repack(&rucksack);
+            assert_eq!(
+                repacked.as_ref().map(|r| r.1),
+                fewest_moves(&rucksack),
+                "{rucksack}"
+            );
+            if let Some((repacked, _)) = repacked {
+                assert_repacked(&rucksack, &repacked);
+            }
+        }
+    }
+
+    #[test]
+    fn test_plan() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let rucksacks = parse_rucksacks(&input).unwrap();
+        let plan = plan(&rucksacks, None);
+        assert!(plan.unfixable.is_empty() && plan.exchanges.is_empty());
+        assert_eq!(plan.saved, 157);
+        let moves: usize = rucksacks.iter().map(|r| repack(r).unwrap().1).sum();
+        assert_eq!(plan.moves, moves);
+
+        // aaab can't be split in two on its own, but can with a c from its
+        // neighbour
+        let rucksacks = ["aaab", "cdcd", "aaab"];
+        let alone = super::plan(&rucksacks, None);
+        assert_eq!(alone.unfixable, [0, 2]);
+        assert_eq!(alone.rucksacks[0], "aaab");
+        assert_eq!(alone.saved, 3 + 4);
+
+        let grouped = super::plan(&rucksacks, Some(2));
+        assert_eq!(grouped.unfixable, [2]);
+        assert_eq!(grouped.exchanges.len(), 1);
+        let Exchange {
+            from,
+            to,
+            gave,
+            took,
+        } = grouped.exchanges[0];
+        assert_eq!((from, to, gave), (0, 1, 'a'));
+        assert!(took == 'c' || took == 'd');
+        assert_eq!(misplaced(&grouped.rucksacks[0]), ItemSet::EMPTY);
+        assert_eq!(misplaced(&grouped.rucksacks[1]), ItemSet::EMPTY);
+        assert_eq!(grouped.saved, 1 + 3 + 4);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::Day04;
+use aoc_common::{parse_number, ParseError, Result, Solution};
+use std::ops::RangeInclusive;
+
+// A line of any number of elves' section assignments, like "2-4,6-8,3-3".
+pub fn parse_group(line: &str) -> Result<Vec<RangeInclusive<usize>>> {
+    line.split(',')
+        .map(|range| {
+            let (start, end) = range.split_once('-').ok_or_else(|| {
+                let message = "expected a range of sections like 2-4";
+                ParseError::new(Day04::DAY, line, range, message)
+            })?;
+            let start = parse_number(Day04::DAY, line, start)?;
+            let end = parse_number(Day04::DAY, line, end)?;
+            if end < start {
+                let message = "the range ends before it starts";
+                return Err(ParseError::new(Day04::DAY, line, range, message));
+            }
+            Ok(start..=end)
+        })
+        .collect()
+}
+
+pub fn parse_groups(input: &str) -> Result<Vec<Vec<RangeInclusive<usize>>>> {
+    input
+        .trim()
+        .split('\n')
+        .map(|line| parse_group(line).map_err(|error| error.within(input, line)))
+        .collect()
+}
+
+// A stretch of sections that the same number of elves cover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    pub start: usize,
+    pub end: usize,
+    pub elves: usize,
+}
+
+// How the sections from the lowest assigned to the highest are covered, as
+// runs of sections in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coverage {
+    pub runs: Vec<Run>,
+}
+
+impl Coverage {
+    // Sweeps across every assignment in the file, counting the elves that
+    // start and stop at each section. An elf stops at the section after its
+    // last, which is kept as a u128 since it's past usize::MAX for an elf
+    // assigned the highest section there is.
+    pub fn new<'a>(groups: impl IntoIterator<Item = &'a Vec<RangeInclusive<usize>>>) -> Self {
+        let mut events: Vec<(u128, isize)> = groups
+            .into_iter()
+            .flatten()
+            .flat_map(|range| [(*range.start() as u128, 1), (*range.end() as u128 + 1, -1)])
+            .collect();
+        events.sort_unstable();
+
+        let mut runs = vec![];
+        let mut elves = 0;
+        let mut events = events.into_iter().peekable();
+        while let Some((section, change)) = events.next() {
+            elves += change;
+            // apply every change at this section before starting a run
+            if events.peek().is_some_and(|(next, _)| *next == section) {
+                continue;
+            }
+            if let Some(&(next, _)) = events.peek() {
+                runs.push(Run {
+                    start: section as usize,
+                    end: (next - 1) as usize,
+                    elves: elves as usize,
+                });
+            }
+        }
+
+        Self { runs }
+    }
+
+    // how many elves cover the section
+    pub fn elves(&self, section: usize) -> usize {
+        let i = self.runs.partition_point(|run| run.end < section);
+        self.runs
+            .get(i)
+            .filter(|run| run.start <= section)
+            .map_or(0, |run| run.elves)
+    }
+
+    // the most elves covering any one section, and the runs where they do
+    pub fn busiest(&self) -> (usize, Vec<Run>) {
+        let most = self.runs.iter().map(|run| run.elves).max().unwrap_or(0);
+        let runs = self.runs.iter().filter(|run| run.elves == most);
+        (most, runs.copied().collect())
+    }
+
+    // the sections between the lowest and highest assigned that nobody covers
+    pub fn uncovered(&self) -> Vec<RangeInclusive<usize>> {
+        self.runs
+            .iter()
+            .filter(|run| run.elves == 0)
+            .map(|run| run.start..=run.end)
+            .collect()
+    }
+}
+
+// The elves on a line (numbered from 0) whose sections the others on the
+// line cover between them, so that they could be left out.
+pub fn redundant(group: &[RangeInclusive<usize>]) -> Vec<usize> {
+    (0..group.len())
+        .filter(|&i| {
+            let mut others: Vec<_> = group
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, range)| range.clone())
+                .collect();
+            others.sort_unstable_by_key(|range| *range.start());
+
+            // walk along the elf's sections as far as the others reach
+            let mut next = *group[i].start();
+            for range in others {
+                if *range.start() > next {
+                    break;
+                }
+                match range.end().checked_add(1) {
+                    Some(after) => next = next.max(after),
+                    // the others reach the highest section there is
+                    None => return true,
+                }
+            }
+            next > *group[i].end()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_parse_groups() {
+        let groups = parse_groups("2-4\n1-1,2-3,4-9\n").unwrap();
+        assert_eq!(groups, [vec![2..=4], vec![1..=1, 2..=3, 4..=9]]);
+
+        let error = parse_groups("2-4\n1-1,3,4-9").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+        let error = parse_groups("2-4,6-5").unwrap_err();
+        assert_eq!(error.message, "the range ends before it starts");
+    }
+
+    #[test]
+    fn test_coverage() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read test input");
+        let coverage = Coverage::new(&parse_groups(&input).unwrap());
+
+        // 2-4,6-8 2-3,4-5 5-7,7-9 2-8,3-7 6-6,4-6 2-6,4-8
+        let elves: Vec<usize> = (1..=10).map(|section| coverage.elves(section)).collect();
+        assert_eq!(elves, [0, 4, 5, 7, 7, 8, 6, 4, 1, 0]);
+        assert_eq!(coverage.busiest().0, 8);
+        assert_eq!(
+            coverage.busiest().1,
+            [Run {
+                start: 6,
+                end: 6,
+                elves: 8
+            }]
+        );
+        assert!(coverage.uncovered().is_empty());
+
+        let coverage = Coverage::new(&parse_groups("1-2,8-9\n4-4").unwrap());
+        assert_eq!(coverage.uncovered(), [3..=3, 5..=7]);
+        assert_eq!(coverage.elves(1), 1);
+        assert_eq!(coverage.elves(100), 0);
+
+        // elves can cover the highest section there is
+        let highest = usize::MAX;
+        let groups = parse_groups(&format!("1-2,{highest}-{highest}\n5-{highest}")).unwrap();
+        let coverage = Coverage::new(&groups);
+        assert_eq!(coverage.uncovered(), [3..=4]);
+        assert_eq!(coverage.elves(highest), 2);
+        assert_eq!(
+            coverage.busiest(),
+            (
+                2,
+                vec![Run {
+                    start: highest,
+                    end: highest,
+                    elves: 2
+                }]
+            )
+        );
+    }
+
+    #[test]
+    fn test_redundant() {
+        assert!(redundant(&[2..=4, 6..=8]).is_empty());
+        assert_eq!(redundant(&[2..=8, 3..=7]), [1]);
+        assert_eq!(redundant(&[1..=3, 2..=5, 4..=6]), [1]);
+        assert_eq!(redundant(&[1..=3, 5..=6, 1..=6]), [0, 1]);
+        assert_eq!(redundant(&[1..=2, 1..=2]), [0, 1]);
+        assert_eq!(redundant(&[1..=3, 5..=6, 2..=6]), [1]);
+        assert_eq!(redundant(&[1..=usize::MAX, 4..=usize::MAX]), [1]);
+    }
+}
@@ -0,0 +1,32 @@
+// Generates inputs in the puzzle's format: one pair of section assignments
+// per line, like "2-4,6-8". `size` is the number of pairs.
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut section_range = || {
+        let start = rng.range(1..=99);
+        let end = rng.range(start..=99);
+        format!("{start}-{end}")
+    };
+
+    (0..size.max(1))
+        .map(|_| format!("{},{}\n", section_range(), section_range()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day04;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 100);
+            let assignments = Day04::parse(&input).unwrap();
+            assert_eq!(assignments.len(), 100);
+            assert!(assignments.iter().all(|(a, b, c, d)| a <= b && c <= d));
+        }
+    }
+}
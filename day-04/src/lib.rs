@@ -0,0 +1,144 @@
+pub mod coverage;
+pub mod generator;
+pub mod rebalance;
+
+use aoc_common::{parse_number, ParseError, Result, Solution};
+
+pub fn parse_ranges(line: &str) -> Result<(usize, usize, usize, usize)> {
+    let mut iter = line
+        .split(['-', ','])
+        .map(|s| parse_number(Day04::DAY, line, s));
+
+    let mut next = || {
+        iter.next().unwrap_or_else(|| {
+            let message = "expected a pair of ranges like 2-4,6-8";
+            Err(ParseError::new(Day04::DAY, line, line, message))
+        })
+    };
+
+    let ranges = (next()?, next()?, next()?, next()?);
+    if iter.next().is_some() {
+        let message = "expected a pair of ranges like 2-4,6-8";
+        return Err(ParseError::new(Day04::DAY, line, line, message));
+    }
+    Ok(ranges)
+}
+
+pub fn parse_assignments(input: &str) -> Result<Vec<(usize, usize, usize, usize)>> {
+    input
+        .trim()
+        .split('\n')
+        .map(|line| parse_ranges(line).map_err(|error| error.within(input, line)))
+        .collect()
+}
+
+pub fn count_overlaps(input: &str) -> Result<(usize, usize)> {
+    let assignments = parse_assignments(input)?;
+    let mut num_fully_contained = 0;
+    let mut num_overlapping = 0;
+
+    for (min_a, max_a, min_b, max_b) in assignments {
+        if fully_contained(min_a, max_a, min_b, max_b) {
+            num_fully_contained += 1;
+        }
+        if overlapping(min_a, max_a, min_b, max_b) {
+            num_overlapping += 1;
+        }
+    }
+
+    Ok((num_fully_contained, num_overlapping))
+}
+
+pub fn fully_contained(min_a: usize, max_a: usize, min_b: usize, max_b: usize) -> bool {
+    min_a >= min_b && max_a <= max_b || min_b >= min_a && max_b <= max_a
+}
+
+pub fn overlapping(min_a: usize, max_a: usize, min_b: usize, max_b: usize) -> bool {
+    !(max_a < min_b || max_b < min_a)
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: usize = 4;
+
+    type Input<'a> = Vec<(usize, usize, usize, usize)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse_assignments(input)
+    }
+
+    fn part_1(assignments: &Vec<(usize, usize, usize, usize)>) -> usize {
+        assignments
+            .iter()
+            .filter(|(min_a, max_a, min_b, max_b)| fully_contained(*min_a, *max_a, *min_b, *max_b))
+            .count()
+    }
+
+    fn part_2(assignments: &Vec<(usize, usize, usize, usize)>) -> usize {
+        assignments
+            .iter()
+            .filter(|(min_a, max_a, min_b, max_b)| overlapping(*min_a, *max_a, *min_b, *max_b))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::fs;
+
+    #[test]
+    fn test_fully_contained() {
+        assert!(!fully_contained(2, 4, 6, 8));
+        assert!(fully_contained(2, 8, 3, 7));
+    }
+
+    #[test]
+    fn test_overlapping() {
+        assert!(!overlapping(2, 4, 6, 8));
+        assert!(overlapping(5, 7, 7, 9));
+    }
+
+    #[test]
+    fn test_count_overlaps() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read test input");
+        assert_eq!(count_overlaps(&input).unwrap(), (2, 4));
+    }
+
+    #[test]
+    fn test_parse_assignments() {
+        let error = parse_assignments("2-4,6-8\n2-3,4-x5").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 7));
+        assert_eq!(error.snippet, "x5");
+
+        let error = parse_assignments("2-4,6-8\n2-3").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    // two sections, each at most 50
+    fn range() -> impl Strategy<Value = (usize, usize)> {
+        (0..50usize, 0..50usize).prop_map(|(a, b)| (a.min(b), a.max(b)))
+    }
+
+    proptest! {
+        #[test]
+        fn ranges_round_trip((min_a, max_a) in range(), (min_b, max_b) in range()) {
+            let line = format!("{min_a}-{max_a},{min_b}-{max_b}");
+            prop_assert_eq!(parse_ranges(&line), Ok((min_a, max_a, min_b, max_b)));
+        }
+
+        #[test]
+        fn overlaps_match_shared_sections((min_a, max_a) in range(), (min_b, max_b) in range()) {
+            let shared = (min_a..=max_a).filter(|section| (min_b..=max_b).contains(section));
+            let contained = (min_a..=max_a).all(|section| (min_b..=max_b).contains(&section))
+                || (min_b..=max_b).all(|section| (min_a..=max_a).contains(&section));
+
+            prop_assert_eq!(overlapping(min_a, max_a, min_b, max_b), shared.count() > 0);
+            prop_assert_eq!(fully_contained(min_a, max_a, min_b, max_b), contained);
+        }
+    }
+}
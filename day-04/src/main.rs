@@ -1,76 +1,150 @@
 // Advent of Code 2022: Day 4
 // https://adventofcode.com/2022/day/4
-// Usage: `cargo run <input-file>`
+// Usage: `cargo run <input-file|directory|->... [--format text|json] [--coverage | --rebalance]`
+//
+// With `--coverage` each line may assign any number of ranges, and rather
+// than the answers this prints how many elves cover each stretch of sections
+// across the whole file, the most covering any one section, the sections
+// nobody covers, and the elves on each line whose sections the others on the
+// line already cover. `--rebalance` instead reassigns the pairs so that no
+// pair shares a section, changing as few sections as possible, and prints
+// the new assignments in the input's format.
 
-use std::env;
-use std::fs;
+use aoc_common::{for_each_input, solve_inputs, Args, Format};
+use day_04::coverage::{parse_groups, redundant, Coverage, Run};
+use day_04::rebalance::{parse_pairs, plan};
+use day_04::Day04;
+use serde_json::json;
+use std::ops::RangeInclusive;
 
 fn main() {
-    let input_filename = env::args().nth(1).expect("please supply an input filename");
-    let input = fs::read_to_string(&input_filename).expect("failed to read input file");
-    let (fully_contained, overlapping) = count_overlaps(&input);
-
-    println!("Number of assignments fully contained within each other: {fully_contained}");
-    println!("Number of overlapping assignments: {overlapping}");
-}
+    let args = Args::from_env_with(&["--coverage", "--rebalance"]);
+    if args.has("--coverage") {
+        print_coverage(&args);
+        return;
+    }
+    if args.has("--rebalance") {
+        print_rebalanced(&args);
+        return;
+    }
 
-fn parse_ranges(line: &str) -> (usize, usize, usize, usize) {
-    let mut iter = line
-        .split(['-', ','])
-        .map(|s| s.parse::<usize>().expect("invalid int"));
+    solve_inputs::<Day04>(&args, |report| {
+        println!(
+            "Number of assignments fully contained within each other: {}",
+            report.part_1
+        );
 
-    (
-        iter.next().expect("malformed range"),
-        iter.next().expect("malformed range"),
-        iter.next().expect("malformed range"),
-        iter.next().expect("malformed range"),
-    )
+        println!("Number of overlapping assignments: {}", report.part_2);
+    });
 }
 
-fn count_overlaps(input: &str) -> (usize, usize) {
-    let assignments = input.trim().split('\n').map(parse_ranges);
-    let mut num_fully_contained = 0;
-    let mut num_overlapping = 0;
+fn print_coverage(args: &Args) {
+    for_each_input(args, |source, input| {
+        let groups = parse_groups(input).map_err(|error| error.to_string())?;
 
-    for (min_a, max_a, min_b, max_b) in assignments {
-        if fully_contained(min_a, max_a, min_b, max_b) {
-            num_fully_contained += 1;
-        }
-        if overlapping(min_a, max_a, min_b, max_b) {
-            num_overlapping += 1;
-        }
-    }
+        let coverage = Coverage::new(&groups);
+        let (most, busiest) = coverage.busiest();
+        let uncovered = coverage.uncovered();
+        // the lines with redundant elves, both numbered from 1
+        let redundant: Vec<(usize, Vec<usize>)> = groups
+            .iter()
+            .enumerate()
+            .map(|(line, group)| {
+                let elves = redundant(group).into_iter().map(|elf| elf + 1).collect();
+                (line + 1, elves)
+            })
+            .filter(|(_, elves): &(usize, Vec<usize>)| !elves.is_empty())
+            .collect();
 
-    (num_fully_contained, num_overlapping)
+        match args.format {
+            Format::Json => {
+                let runs = |runs: &[Run]| -> Vec<_> {
+                    runs.iter()
+                        .map(|Run { start, end, elves }| json!({"start": start, "end": end, "elves": elves}))
+                        .collect()
+                };
+                let report = json!({
+                    "day": 4,
+                    "input": source.to_string(),
+                    "coverage": runs(&coverage.runs),
+                    "most_elves": most,
+                    "busiest": runs(&busiest),
+                    "uncovered": uncovered.iter().map(|range| json!({"start": range.start(), "end": range.end()})).collect::<Vec<_>>(),
+                    "redundant": redundant.iter().map(|(line, elves)| json!({"line": line, "elves": elves})).collect::<Vec<_>>(),
+                });
+                println!("{report}");
+            }
+            Format::Text => {
+                println!("Elves covering each section:");
+                for &Run { start, end, elves } in &coverage.runs {
+                    println!("  {}: {elves}", sections(&(start..=end)));
+                }
+                let busiest: Vec<String> = busiest
+                    .iter()
+                    .map(|run| sections(&(run.start..=run.end)))
+                    .collect();
+                println!("Most elves: {most}, at {}", busiest.join(", "));
+                let uncovered: Vec<String> = uncovered.iter().map(sections).collect();
+                match uncovered.len() {
+                    0 => println!("Uncovered: nothing"),
+                    _ => println!("Uncovered: {}", uncovered.join(", ")),
+                }
+                for (line, elves) in &redundant {
+                    let elves: Vec<String> = elves.iter().map(|elf| elf.to_string()).collect();
+                    match elves.len() {
+                        1 => println!("Line {line}: elf {} is redundant", elves[0]),
+                        _ => println!("Line {line}: elves {} are redundant", elves.join(", ")),
+                    }
+                }
+            }
+        }
+        Ok(())
+    });
 }
 
-fn fully_contained(min_a: usize, max_a: usize, min_b: usize, max_b: usize) -> bool {
-    min_a >= min_b && max_a <= max_b || min_b >= min_a && max_b <= max_a
-}
+fn print_rebalanced(args: &Args) {
+    for_each_input(args, |source, input| {
+        let assignments = parse_pairs(input).map_err(|error| error.to_string())?;
+        let plan = plan(&assignments);
+        let lines: Vec<String> = plan
+            .assignments
+            .iter()
+            .map(|(min_a, max_a, min_b, max_b)| format!("{min_a}-{max_a},{min_b}-{max_b}"))
+            .collect();
 
-fn overlapping(min_a: usize, max_a: usize, min_b: usize, max_b: usize) -> bool {
-    !(max_a < min_b || max_b < min_a)
+        match args.format {
+            Format::Json => {
+                let report = json!({
+                    "day": 4,
+                    "input": source.to_string(),
+                    "assignments": lines,
+                    "changed": plan.changed,
+                    "pairs_changed": plan.pairs_changed.iter().map(|i| i + 1).collect::<Vec<_>>(),
+                });
+                println!("{report}");
+            }
+            Format::Text => {
+                // the assignments go to stdout in the input's format, so
+                // that they can be saved and solved again
+                for line in &lines {
+                    println!("{line}");
+                }
+                eprintln!(
+                    "Changed {} sections in {} of {} pairs",
+                    plan.changed,
+                    plan.pairs_changed.len(),
+                    lines.len()
+                );
+            }
+        }
+        Ok(())
+    });
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_fully_contained() {
-        assert!(!fully_contained(2, 4, 6, 8));
-        assert!(fully_contained(2, 8, 3, 7));
-    }
-
-    #[test]
-    fn test_overlapping() {
-        assert!(!overlapping(2, 4, 6, 8));
-        assert!(overlapping(5, 7, 7, 9));
-    }
-
-    #[test]
-    fn test_count_overlaps() {
-        let input = fs::read_to_string("test-input.txt").expect("failed to read test input");
-        assert_eq!(count_overlaps(&input), (2, 4));
+// like "4" or "2-8"
+fn sections(range: &RangeInclusive<usize>) -> String {
+    match range.start() == range.end() {
+        true => range.start().to_string(),
+        false => format!("{}-{}", range.start(), range.end()),
     }
 }
@@ -0,0 +1,226 @@
+use crate::{overlapping, parse_ranges, Day04};
+use aoc_common::{ParseError, Result, Solution};
+
+// A pair of assignments as (min_a, max_a, min_b, max_b), as parsed.
+pub type Pair = (usize, usize, usize, usize);
+
+// A pair like parse_ranges reads, with neither range ending before it starts.
+pub fn parse_pair(line: &str) -> Result<Pair> {
+    let pair @ (min_a, max_a, min_b, max_b) = parse_ranges(line)?;
+    for (range, (min, max)) in line.split(',').zip([(min_a, max_a), (min_b, max_b)]) {
+        if max < min {
+            let message = "the range ends before it starts";
+            return Err(ParseError::new(Day04::DAY, line, range, message));
+        }
+    }
+    Ok(pair)
+}
+
+pub fn parse_pairs(input: &str) -> Result<Vec<Pair>> {
+    input
+        .trim()
+        .split('\n')
+        .map(|line| parse_pair(line).map_err(|error| error.within(input, line)))
+        .collect()
+}
+
+// How to reassign a list of pairs so that neither elf in a pair shares a
+// section with the other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub assignments: Vec<Pair>,
+    // sections taken from or given to an elf, over every pair (stopping at
+    // usize::MAX)
+    pub changed: usize,
+    // the pairs that had to change, numbered from 0
+    pub pairs_changed: Vec<usize>,
+}
+
+// The reassignment of a pair that changes the fewest sections with no
+// section in both ranges, and how many it changes. Each elf keeps at least
+// one section, and no elf is given a section lower than the pair had unless
+// there's no room above, since new sections stay below usize::MAX.
+//
+// Whichever elf ends up first, some boundary s splits them, with the first
+// keeping what it had up to s and the second what it had after it. An elf
+// with nothing on its side gives up everything for a single section there
+// instead. The cost of each side only changes slope at the ends of the elf's
+// range, so the best s is next to one of them. A count too big for a usize
+// (only possible when both elves have nearly every section) is usize::MAX.
+pub fn rebalance(pair: Pair) -> (Pair, usize) {
+    let (min_a, max_a, min_b, max_b) = pair;
+    if !overlapping(min_a, max_a, min_b, max_b) {
+        return (pair, 0);
+    }
+    let lowest = min_a.min(min_b);
+
+    // the best reassignment so far, with its cost as (whether it's too big
+    // to count, how many it changes, whether an elf went below the pair)
+    let mut best: Option<(Pair, (bool, usize, bool))> = None;
+    for a_first in [true, false] {
+        let ((min_1, max_1), (min_2, max_2)) = match a_first {
+            true => ((min_a, max_a), (min_b, max_b)),
+            false => ((min_b, max_b), (min_a, max_a)),
+        };
+        let candidates = [min_1, max_1, min_2, max_2]
+            .into_iter()
+            .flat_map(|end| [end.saturating_sub(1), end]);
+        for s in candidates {
+            let (first, lost_1) = match s >= min_1 {
+                true => ((min_1, max_1.min(s)), Some(max_1 - max_1.min(s))),
+                false => ((s, s), (max_1 - min_1).checked_add(2)),
+            };
+            let (second, lost_2) = match s < max_2 {
+                true => ((min_2.max(s + 1), max_2), Some(min_2.max(s + 1) - min_2)),
+                false if s < usize::MAX - 1 => ((s + 1, s + 1), (max_2 - min_2).checked_add(2)),
+                false => continue,
+            };
+            let pair = match a_first {
+                true => (first.0, first.1, second.0, second.1),
+                false => (second.0, second.1, first.0, first.1),
+            };
+            let changed = lost_1.zip(lost_2).and_then(|(a, b)| a.checked_add(b));
+            let cost = (changed.is_none(), changed.unwrap_or(usize::MAX), s < lowest);
+            if best.is_none_or(|(_, fewest)| cost < fewest) {
+                best = Some((pair, cost));
+            }
+        }
+    }
+    let (pair, (_, changed, _)) =
+        best.expect("the first elf can always keep its lowest section or go just below it");
+    (pair, changed)
+}
+
+pub fn plan(assignments: &[Pair]) -> Plan {
+    let mut changed: usize = 0;
+    let mut pairs_changed = vec![];
+    let assignments = assignments
+        .iter()
+        .enumerate()
+        .map(|(i, &pair)| {
+            let (pair, n) = rebalance(pair);
+            if n > 0 {
+                changed = changed.saturating_add(n);
+                pairs_changed.push(i);
+            }
+            pair
+        })
+        .collect();
+
+    Plan {
+        assignments,
+        changed,
+        pairs_changed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    // sections in one range or the other but not both
+    fn changed((min, max): (usize, usize), (new_min, new_max): (usize, usize)) -> usize {
+        let (old, new) = (min..=max, new_min..=new_max);
+        (min.min(new_min)..=max.max(new_max))
+            .filter(|section| old.contains(section) != new.contains(section))
+            .count()
+    }
+
+    #[test]
+    fn test_rebalance() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read test input");
+        let plan = plan(&parse_pairs(&input).unwrap());
+        assert_eq!(
+            plan.assignments,
+            [
+                (2, 4, 6, 8),
+                (2, 3, 4, 5),
+                (5, 6, 7, 9),
+                (2, 2, 3, 7),
+                (6, 6, 4, 5),
+                (2, 5, 6, 8)
+            ]
+        );
+        assert_eq!(plan.pairs_changed, [2, 3, 4, 5]);
+        assert_eq!(plan.changed, 1 + 6 + 1 + 3);
+
+        // moving a short assignment out of the way beats cutting a long one
+        assert_eq!(rebalance((1, 5, 3, 3)), ((1, 5, 6, 6), 2));
+        assert_eq!(rebalance((1, 1, 1, 1)), ((1, 1, 2, 2), 2));
+
+        // with no room above, an elf goes below instead
+        let highest = usize::MAX - 1;
+        assert_eq!(
+            rebalance((highest, highest, highest, highest)),
+            ((highest - 1, highest - 1, highest, highest), 2)
+        );
+        assert_eq!(
+            rebalance((0, highest, 0, highest)),
+            ((0, 0, 1, highest), usize::MAX)
+        );
+    }
+
+    #[test]
+    fn rebalanced_pairs_parse() {
+        let highest = usize::MAX - 1;
+        let pairs = [
+            (1, 1, 1, 1),
+            (highest, highest, highest, highest),
+            (highest - 1, highest, highest, highest),
+            (0, highest, 0, highest),
+            (5, highest, 3, highest - 2),
+            (usize::MAX, usize::MAX, usize::MAX, usize::MAX),
+            (0, usize::MAX, 0, usize::MAX),
+        ];
+        for pair in pairs {
+            let (new @ (min_a, max_a, min_b, max_b), _) = rebalance(pair);
+            let line = format!("{min_a}-{max_a},{min_b}-{max_b}");
+            assert_eq!(parse_pair(&line), Ok(new));
+            // new sections stay below usize::MAX, though old ones may be it
+            let (_, old_max_a, _, old_max_b) = pair;
+            assert!(
+                max_a.max(max_b) <= highest.max(old_max_a.max(old_max_b)),
+                "{line}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_pairs() {
+        // a reversed range is an error, rather than a pair to rebalance
+        let error = parse_pairs("2-4,6-8\n5-2,0-10").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "the range ends before it starts");
+        let error = parse_pairs("2-4,8-6").unwrap_err();
+        assert_eq!((error.column, error.snippet.as_str()), (5, "8-6"));
+    }
+
+    #[test]
+    fn test_rebalance_changes_fewest() {
+        let ranges: Vec<(usize, usize)> = (0..=6)
+            .flat_map(|min| (min..=6).map(move |max| (min, max)))
+            .collect();
+        // the new ranges can reach a little past the old ones
+        let new_ranges: Vec<(usize, usize)> = (0..=8)
+            .flat_map(|min| (min..=8).map(move |max| (min, max)))
+            .collect();
+
+        for &a in &ranges {
+            for &b in &ranges {
+                let ((min_a, max_a, min_b, max_b), n) = rebalance((a.0, a.1, b.0, b.1));
+                assert!(!overlapping(min_a, max_a, min_b, max_b));
+                assert!(min_a <= max_a && min_b <= max_b);
+                assert_eq!(n, changed(a, (min_a, max_a)) + changed(b, (min_b, max_b)));
+
+                let fewest = new_ranges
+                    .iter()
+                    .flat_map(|&new_a| new_ranges.iter().map(move |&new_b| (new_a, new_b)))
+                    .filter(|(new_a, new_b)| !overlapping(new_a.0, new_a.1, new_b.0, new_b.1))
+                    .map(|(new_a, new_b)| changed(a, new_a) + changed(b, new_b))
+                    .min();
+                assert_eq!(Some(n), fewest, "{a:?} {b:?}");
+            }
+        }
+    }
+}
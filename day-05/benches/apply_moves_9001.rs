@@ -1,19 +1,78 @@
+use aoc_common::Rng;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use day_05::{apply_moves_9001, parse_input};
+use day_05::rope::RopeStacks;
+use day_05::{apply_moves_9001, parse_input, Move};
 use std::fs;
 
+// Nine stacks of `height` crates and moves that each take a random share of
+// a stack, so that moves are as big as the stacks.
+fn huge_moves(height: usize, num_moves: usize) -> (Vec<Vec<String>>, Vec<Move>) {
+    let mut rng = Rng::new(0);
+    let stacks: Vec<Vec<String>> = (0..9)
+        .map(|_| (0..height).map(|i| (i % 26).to_string()).collect())
+        .collect();
+    let mut heights = [height; 9];
+    let moves = (0..num_moves)
+        .map(|_| {
+            let from = rng.below(9);
+            let to = (from + rng.range(1..=8)) % 9;
+            let quantity = rng.range(0..=heights[from]);
+            heights[from] -= quantity;
+            heights[to] += quantity;
+            Move::new(quantity, from + 1, to + 1)
+        })
+        .collect();
+    (stacks, moves)
+}
+
 pub fn criterion_benchmark(c: &mut Criterion) {
     let input = fs::read_to_string("input.txt").expect("failed to read input");
-    let (original_stacks, moves) = parse_input(&input);
+    let (original_stacks, moves) = parse_input(&input).expect("failed to parse input");
     c.bench_function("apply_moves_9001", |b| {
         b.iter_batched(
             || original_stacks.clone(),
             |mut stacks| {
-                apply_moves_9001(black_box(&mut stacks), black_box(&moves));
+                apply_moves_9001(black_box(&mut stacks), black_box(&moves)).unwrap();
+            },
+            criterion::BatchSize::SmallInput,
+        )
+    });
+    c.bench_function("apply_moves_9001 with rope stacks", |b| {
+        b.iter_batched(
+            || RopeStacks::new(&original_stacks),
+            |mut stacks| {
+                black_box(&mut stacks)
+                    .apply_moves_9001(black_box(&moves))
+                    .unwrap();
             },
             criterion::BatchSize::SmallInput,
         )
     });
+
+    let (huge_stacks, huge_moves) = huge_moves(100_000, 10_000);
+    let mut group = c.benchmark_group("huge moves");
+    group.sample_size(10);
+    group.bench_function("apply_moves_9001", |b| {
+        b.iter_batched(
+            || huge_stacks.clone(),
+            |mut stacks| {
+                apply_moves_9001(black_box(&mut stacks), black_box(&huge_moves)).unwrap();
+            },
+            criterion::BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("apply_moves_9001 with rope stacks", |b| {
+        b.iter_batched(
+            || RopeStacks::new(&huge_stacks),
+            |mut stacks| {
+                black_box(&mut stacks)
+                    .apply_moves_9001(black_box(&huge_moves))
+                    .unwrap();
+            },
+            criterion::BatchSize::LargeInput,
+        )
+    });
+    group.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
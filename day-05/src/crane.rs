@@ -0,0 +1,321 @@
+use crate::{check_move, Move, MoveError};
+
+// A model of crane. Each applies a move by lifting crates off the top of one
+// stack and putting them on top of another, and models differ in how many
+// crates they lift at once and which way up they put them down.
+pub trait CrateMover {
+    fn name(&self) -> String;
+
+    // Moves `quantity` crates from the top of `from` to the top of `to`.
+    // Moves are made through apply_moves, which checks each one first, so
+    // `from` always has at least `quantity` crates; a crane may panic if
+    // it's called with fewer.
+    fn move_crates(&mut self, from: &mut Vec<String>, to: &mut Vec<String>, quantity: usize);
+
+    // Applies the moves in order, stopping at the first that can't be made.
+    // Stacks are numbered from 1, and moving a stack onto itself leaves it
+    // as it is.
+    fn apply_moves(&mut self, stacks: &mut [Vec<String>], moves: &[Move]) -> Result<(), MoveError> {
+        for (i, moov) in moves.iter().enumerate() {
+            check_move(moov, i + 1, stacks.len(), |stack| stacks[stack].len())?;
+            if moov.from == moov.to {
+                continue;
+            }
+            let (from, to) = two_stacks(stacks, moov.from - 1, moov.to - 1);
+            self.move_crates(from, to, moov.quantity);
+        }
+        Ok(())
+    }
+}
+
+fn two_stacks(
+    stacks: &mut [Vec<String>],
+    from: usize,
+    to: usize,
+) -> (&mut Vec<String>, &mut Vec<String>) {
+    if from < to {
+        let (left, right) = stacks.split_at_mut(to);
+        (&mut left[from], &mut right[0])
+    } else {
+        let (left, right) = stacks.split_at_mut(from);
+        (&mut right[0], &mut left[to])
+    }
+}
+
+// Lifts the top `quantity` crates of `from` together and puts them on `to`,
+// in the same order or upside down.
+fn lift(from: &mut Vec<String>, to: &mut Vec<String>, quantity: usize, reversed: bool) {
+    let start = from.len().checked_sub(quantity).expect("invalid move");
+    let crates = from.drain(start..);
+    if reversed {
+        to.extend(crates.rev());
+    } else {
+        to.extend(crates);
+    }
+}
+
+// Part 1's crane, which moves one crate at a time.
+pub struct CrateMover9000;
+
+impl CrateMover for CrateMover9000 {
+    fn name(&self) -> String {
+        String::from("CrateMover 9000")
+    }
+
+    fn move_crates(&mut self, from: &mut Vec<String>, to: &mut Vec<String>, quantity: usize) {
+        for _ in 0..quantity {
+            let krate = from.pop().expect("invalid move");
+            to.push(krate)
+        }
+    }
+}
+
+// Part 2's crane, which moves every crate at once.
+pub struct CrateMover9001;
+
+impl CrateMover for CrateMover9001 {
+    fn name(&self) -> String {
+        String::from("CrateMover 9001")
+    }
+
+    fn move_crates(&mut self, from: &mut Vec<String>, to: &mut Vec<String>, quantity: usize) {
+        lift(from, to, quantity, false);
+    }
+}
+
+// Lifts at most `capacity` crates at a time, keeping each batch in order, so
+// a large move is made as several smaller ones.
+pub struct LimitedCapacity {
+    capacity: usize,
+}
+
+impl LimitedCapacity {
+    // None for a capacity of 0, since a crane has to lift at least one crate
+    pub fn new(capacity: usize) -> Option<Self> {
+        (capacity > 0).then_some(Self { capacity })
+    }
+}
+
+impl CrateMover for LimitedCapacity {
+    fn name(&self) -> String {
+        format!("a crane lifting at most {}", self.capacity)
+    }
+
+    fn move_crates(&mut self, from: &mut Vec<String>, to: &mut Vec<String>, quantity: usize) {
+        let mut left = quantity;
+        while left > 0 {
+            let batch = left.min(self.capacity);
+            lift(from, to, batch, false);
+            left -= batch;
+        }
+    }
+}
+
+// Like LimitedCapacity, but puts every other batch of a move down upside
+// down, starting with the second.
+pub struct Alternating {
+    capacity: usize,
+}
+
+impl Alternating {
+    // None for a capacity of 0, as with LimitedCapacity
+    pub fn new(capacity: usize) -> Option<Self> {
+        (capacity > 0).then_some(Self { capacity })
+    }
+}
+
+impl CrateMover for Alternating {
+    fn name(&self) -> String {
+        format!(
+            "a crane lifting at most {}, turning every other batch over",
+            self.capacity
+        )
+    }
+
+    fn move_crates(&mut self, from: &mut Vec<String>, to: &mut Vec<String>, quantity: usize) {
+        let mut left = quantity;
+        let mut reversed = false;
+        while left > 0 {
+            let batch = left.min(self.capacity);
+            lift(from, to, batch, reversed);
+            left -= batch;
+            reversed = !reversed;
+        }
+    }
+}
+
+// A crane that lifts batches following a pattern, like "2r,1": two crates
+// turned over, then one, then two turned over again and so on until the move
+// is done. A batch bigger than what's left of the move lifts the rest.
+pub struct Custom {
+    batches: Vec<(usize, bool)>,
+}
+
+impl Custom {
+    pub fn parse(pattern: &str) -> Result<Self, String> {
+        let batches = pattern
+            .split(',')
+            .map(|batch| {
+                let (size, reversed) = match batch.strip_suffix('r') {
+                    Some(size) => (size, true),
+                    None => (batch, false),
+                };
+                match size.parse() {
+                    Ok(size) if size > 0 => Ok((size, reversed)),
+                    _ => Err(format!(
+                        "expected batches like \"2r,1\" (a number of crates, with r to turn them over), got \"{batch}\""
+                    )),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { batches })
+    }
+}
+
+impl CrateMover for Custom {
+    fn name(&self) -> String {
+        let batches: Vec<String> = self
+            .batches
+            .iter()
+            .map(|&(size, reversed)| format!("{size}{}", if reversed { "r" } else { "" }))
+            .collect();
+        format!("a crane lifting {}", batches.join(","))
+    }
+
+    fn move_crates(&mut self, from: &mut Vec<String>, to: &mut Vec<String>, quantity: usize) {
+        let mut left = quantity;
+        for &(size, reversed) in self.batches.iter().cycle() {
+            if left == 0 {
+                break;
+            }
+            let batch = left.min(size);
+            lift(from, to, batch, reversed);
+            left -= batch;
+        }
+    }
+}
+
+// The crane a `--crane` argument names: 9000, 9001, capacity:N,
+// alternating:N or custom:PATTERN.
+pub fn crane(model: &str) -> Result<Box<dyn CrateMover>, String> {
+    let invalid_capacity = |n: &str| format!("expected a capacity of at least 1, got \"{n}\"");
+
+    match model.split_once(':') {
+        None if model == "9000" => Ok(Box::new(CrateMover9000)),
+        None if model == "9001" => Ok(Box::new(CrateMover9001)),
+        Some(("capacity", n)) => match n.parse().ok().and_then(LimitedCapacity::new) {
+            Some(crane) => Ok(Box::new(crane)),
+            None => Err(invalid_capacity(n)),
+        },
+        Some(("alternating", n)) => match n.parse().ok().and_then(Alternating::new) {
+            Some(crane) => Ok(Box::new(crane)),
+            None => Err(invalid_capacity(n)),
+        },
+        Some(("custom", pattern)) => Ok(Box::new(Custom::parse(pattern)?)),
+        _ => Err(format!(
+            "expected 9000, 9001, capacity:N, alternating:N or custom:PATTERN, got \"{model}\""
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, top_of_stacks};
+    use std::fs;
+
+    fn top_with(crane: &mut dyn CrateMover) -> String {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let (mut stacks, moves) = parse_input(&input).unwrap();
+        crane.apply_moves(&mut stacks, &moves).unwrap();
+        top_of_stacks(&stacks)
+    }
+
+    // moves 5 crates, ABCDE from the bottom, onto an empty stack
+    fn five_moved(crane: &mut dyn CrateMover) -> String {
+        let crates = ["A", "B", "C", "D", "E"].map(String::from);
+        let mut stacks = vec![crates.to_vec(), vec![]];
+        crane
+            .apply_moves(&mut stacks, &[Move::new(5, 1, 2)])
+            .unwrap();
+        stacks[1].concat()
+    }
+
+    #[test]
+    fn test_cranes() {
+        assert_eq!(top_with(&mut CrateMover9000), "CMZ");
+        assert_eq!(top_with(&mut CrateMover9001), "MCD");
+        assert_eq!(top_with(&mut LimitedCapacity::new(1).unwrap()), "CMZ");
+        assert_eq!(top_with(&mut LimitedCapacity::new(3).unwrap()), "MCD");
+
+        assert_eq!(five_moved(&mut CrateMover9000), "EDCBA");
+        assert_eq!(five_moved(&mut CrateMover9001), "ABCDE");
+        assert_eq!(five_moved(&mut LimitedCapacity::new(2).unwrap()), "DEBCA");
+        assert_eq!(five_moved(&mut Alternating::new(2).unwrap()), "DECBA");
+        assert_eq!(five_moved(&mut Custom::parse("1,2r").unwrap()), "EDCBA");
+        assert_eq!(five_moved(&mut Custom::parse("3,1").unwrap()), "CDEBA");
+    }
+
+    #[test]
+    fn test_crane() {
+        assert_eq!(crane("9000").unwrap().name(), "CrateMover 9000");
+        assert_eq!(
+            crane("capacity:4").unwrap().name(),
+            "a crane lifting at most 4"
+        );
+        assert_eq!(crane("custom:2r,1").unwrap().name(), "a crane lifting 2r,1");
+
+        assert!(crane("9002").is_err());
+        assert!(crane("capacity:0").is_err());
+        assert!(crane("alternating:0").is_err());
+        assert!(LimitedCapacity::new(0).is_none());
+        assert!(Alternating::new(0).is_none());
+        assert!(crane("custom:2,x").is_err());
+    }
+
+    #[test]
+    fn moving_too_many_crates() {
+        // every crane reports a move it can't make rather than panicking
+        for model in ["9000", "9001", "capacity:2", "alternating:2", "custom:2r,1"] {
+            let mut stacks = vec![vec![String::from("A")], vec![]];
+            let error = crane(model)
+                .unwrap()
+                .apply_moves(&mut stacks, &[Move::new(2, 1, 2)])
+                .unwrap_err();
+            assert_eq!((error.stack, error.heights), (1, vec![1, 0]), "{model}");
+            assert_eq!(stacks, [vec!["A"], vec![]]);
+        }
+    }
+
+    // a crane of its own, which only counts the crates it's asked to move
+    struct Counter(usize);
+
+    impl CrateMover for Counter {
+        fn name(&self) -> String {
+            String::from("a counter")
+        }
+
+        fn move_crates(&mut self, from: &mut Vec<String>, _: &mut Vec<String>, quantity: usize) {
+            assert!(from.len() >= quantity);
+            self.0 += quantity;
+        }
+    }
+
+    #[test]
+    fn cranes_of_our_own() {
+        let mut counter = Counter(0);
+        let mut stacks = vec![vec![String::from("A"), String::from("B")], vec![]];
+        let moves = [Move::new(2, 1, 2), Move::new(1, 1, 2), Move::new(3, 1, 2)];
+        let error = counter.apply_moves(&mut stacks, &moves).unwrap_err();
+        assert_eq!((error.number, counter.0), (3, 3));
+    }
+
+    #[test]
+    fn moving_onto_the_same_stack() {
+        let mut stacks = vec![vec![String::from("A"), String::from("B")]];
+        CrateMover9001
+            .apply_moves(&mut stacks, &[Move::new(2, 1, 1)])
+            .unwrap();
+        assert_eq!(stacks, [["A", "B"]]);
+    }
+}
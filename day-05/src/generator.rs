@@ -0,0 +1,58 @@
+// Generates inputs in the puzzle's format: a drawing of up to nine stacks of
+// crates, a blank line, then moves like "move 1 from 2 to 1" that never take
+// more crates than a stack holds. `size` is the number of moves.
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let num_stacks = rng.range(3..=9);
+    let mut heights: Vec<usize> = (0..num_stacks).map(|_| rng.range(0..=8)).collect();
+    heights[0] = heights[0].max(1);
+
+    // the drawing, from the top of the tallest stack down to the numbers
+    let mut output = String::new();
+    for level in (0..*heights.iter().max().unwrap()).rev() {
+        let row: Vec<String> = heights
+            .iter()
+            .map(|&height| match height > level {
+                true => format!("[{}]", (b'A' + rng.below(26) as u8) as char),
+                false => "   ".to_string(),
+            })
+            .collect();
+        output += row.join(" ").trim_end();
+        output.push('\n');
+    }
+    let numbers: Vec<String> = (1..=num_stacks).map(|i| format!(" {i} ")).collect();
+    output += &numbers.join(" ");
+    output += "\n\n";
+
+    for _ in 0..size.max(1) {
+        let non_empty: Vec<usize> = (0..num_stacks).filter(|&i| heights[i] > 0).collect();
+        let from = *rng.choose(&non_empty);
+        let to = (from + rng.range(1..=num_stacks - 1)) % num_stacks;
+        let quantity = rng.range(1..=heights[from]);
+
+        heights[from] -= quantity;
+        heights[to] += quantity;
+        output += &format!("move {quantity} from {} to {}\n", from + 1, to + 1);
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day05;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 100);
+            let stacks_and_moves = Day05::parse(&input).unwrap();
+            assert_eq!(stacks_and_moves.1.len(), 100);
+            Day05::part_1(&stacks_and_moves);
+            Day05::part_2(&stacks_and_moves);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use crate::crane::CrateMover;
+use crate::{check_move, draw_stacks, Move, MoveError};
+use std::slice;
+
+// A move that was made, with the crates it lifted off the top of one stack
+// and how they landed on the other (both from the bottom up), so that it
+// can be undone and redone whichever crane made it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Step {
+    moov: Move,
+    lifted: Vec<String>,
+    landed: Vec<String>,
+}
+
+// Stacks that remember every move made on them, so that moves can be undone
+// and redone and any step between the first and last can be looked at.
+// Steps are numbered from 1, with step 0 the stacks as they started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History {
+    stacks: Vec<Vec<String>>,
+    steps: Vec<Step>,
+    // how many of the steps have been made, with the rest undone
+    step: usize,
+}
+
+impl History {
+    pub fn new(stacks: Vec<Vec<String>>) -> Self {
+        Self {
+            stacks,
+            steps: vec![],
+            step: 0,
+        }
+    }
+
+    pub fn stacks(&self) -> &[Vec<String>] {
+        &self.stacks
+    }
+
+    // the step the stacks are at
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    // how many steps there are to go through, including undone ones
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    // the move that made the step, if there is one
+    pub fn move_at(&self, step: usize) -> Option<Move> {
+        step.checked_sub(1)
+            .and_then(|i| self.steps.get(i))
+            .map(|step| step.moov)
+    }
+
+    // Makes the move with the crane as the next step, forgetting any steps
+    // that were undone.
+    pub fn apply(&mut self, crane: &mut dyn CrateMover, moov: Move) -> Result<(), MoveError> {
+        let stacks = &mut self.stacks;
+        check_move(&moov, self.step + 1, stacks.len(), |stack| {
+            stacks[stack].len()
+        })?;
+
+        let top = |stack: &Vec<String>| stack[stack.len() - moov.quantity..].to_vec();
+        let lifted = top(&stacks[moov.from - 1]);
+        crane
+            .apply_moves(stacks, slice::from_ref(&moov))
+            .expect("the move was checked");
+        let landed = top(&stacks[moov.to - 1]);
+
+        self.steps.truncate(self.step);
+        self.steps.push(Step {
+            moov,
+            lifted,
+            landed,
+        });
+        self.step += 1;
+        Ok(())
+    }
+
+    // Makes the moves in order, stopping at the first that can't be made.
+    // Moves are numbered in errors by the step they would have made.
+    pub fn apply_all(
+        &mut self,
+        crane: &mut dyn CrateMover,
+        moves: &[Move],
+    ) -> Result<(), MoveError> {
+        moves.iter().try_for_each(|&moov| self.apply(crane, moov))
+    }
+
+    // Takes the last step back, returning its move, or None at step 0.
+    pub fn undo(&mut self) -> Option<Move> {
+        let step = self.steps.get(self.step.checked_sub(1)?)?;
+        let Move { quantity, from, to } = step.moov;
+        let height = self.stacks[to - 1].len();
+        self.stacks[to - 1].truncate(height - quantity);
+        self.stacks[from - 1].extend(step.lifted.iter().cloned());
+        self.step -= 1;
+        Some(step.moov)
+    }
+
+    // Makes the next undone step again, returning its move, or None if
+    // there's nothing to redo.
+    pub fn redo(&mut self) -> Option<Move> {
+        let step = self.steps.get(self.step)?;
+        let Move { quantity, from, to } = step.moov;
+        let height = self.stacks[from - 1].len();
+        self.stacks[from - 1].truncate(height - quantity);
+        self.stacks[to - 1].extend(step.landed.iter().cloned());
+        self.step += 1;
+        Some(step.moov)
+    }
+
+    // Undoes or redoes steps until the stacks are at `step`, or as far as
+    // there are steps to go.
+    pub fn jump_to(&mut self, step: usize) {
+        while self.step > step && self.undo().is_some() {}
+        while self.step < step && self.redo().is_some() {}
+    }
+
+    // the drawing of the stacks at the current step
+    pub fn draw(&self) -> String {
+        draw_stacks(&self.stacks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::{Alternating, CrateMover9000, CrateMover9001};
+    use crate::{apply_moves_9001, parse_input, parse_stacks};
+    use aoc_common::Rng;
+    use std::fs;
+
+    #[test]
+    fn test_undo_redo() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let (stacks, moves) = parse_input(&input).unwrap();
+        let mut history = History::new(stacks.clone());
+        history.apply_all(&mut CrateMover9000, &moves).unwrap();
+        assert_eq!((history.step(), history.len()), (4, 4));
+        let end = history.stacks().to_vec();
+
+        assert_eq!(history.undo(), Some(moves[3]));
+        history.jump_to(0);
+        assert_eq!(history.stacks(), stacks);
+        assert_eq!(history.undo(), None);
+
+        history.jump_to(10);
+        assert_eq!(history.step(), 4);
+        assert_eq!(history.stacks(), end);
+        assert_eq!(history.redo(), None);
+
+        // each step is what applying that many moves makes
+        for step in 0..=moves.len() {
+            history.jump_to(step);
+            let mut expected = stacks.clone();
+            CrateMover9000
+                .apply_moves(&mut expected, &moves[..step])
+                .unwrap();
+            assert_eq!(history.stacks(), expected, "step {step}");
+            assert_eq!(parse_stacks(&history.draw()).unwrap(), expected);
+        }
+
+        // a new move after undoing forgets the undone ones
+        history.jump_to(1);
+        history
+            .apply(&mut CrateMover9001, Move::new(1, 1, 3))
+            .unwrap();
+        assert_eq!((history.step(), history.len()), (2, 2));
+        assert_eq!(history.move_at(2), Some(Move::new(1, 1, 3)));
+        assert_eq!(history.redo(), None);
+    }
+
+    #[test]
+    fn undoing_any_crane() {
+        let mut rng = Rng::new(5);
+        let input = crate::generator::generate(&mut rng, 200);
+        let (stacks, moves) = parse_input(&input).unwrap();
+
+        for crane in [
+            &mut CrateMover9001 as &mut dyn CrateMover,
+            &mut Alternating::new(2).unwrap(),
+        ] {
+            let mut history = History::new(stacks.clone());
+            history.apply_all(crane, &moves).unwrap();
+            history.jump_to(0);
+            assert_eq!(history.stacks(), stacks);
+        }
+
+        let mut history = History::new(stacks.clone());
+        history.apply_all(&mut CrateMover9001, &moves).unwrap();
+        history.jump_to(0);
+        history.jump_to(moves.len());
+        let mut expected = stacks.clone();
+        apply_moves_9001(&mut expected, &moves).unwrap();
+        assert_eq!(history.stacks(), expected);
+    }
+
+    #[test]
+    fn invalid_steps() {
+        let mut history = History::new(vec![vec![String::from("A")], vec![]]);
+        history
+            .apply(&mut CrateMover9000, Move::new(1, 1, 2))
+            .unwrap();
+        let error = history
+            .apply(&mut CrateMover9000, Move::new(1, 1, 2))
+            .unwrap_err();
+        assert_eq!((error.number, error.heights), (2, vec![0, 1]));
+        assert_eq!(history.len(), 1);
+    }
+
+    #[test]
+    fn test_draw() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let drawing = input.split("\n\n").next().unwrap();
+        let history = History::new(parse_stacks(drawing).unwrap());
+        let lines: Vec<&str> = drawing.lines().map(str::trim_end).collect();
+        assert_eq!(history.draw(), lines.join("\n"));
+    }
+}
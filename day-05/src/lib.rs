@@ -1,4 +1,14 @@
-#[derive(Debug, PartialEq, Eq)]
+pub mod crane;
+pub mod generator;
+pub mod history;
+pub mod rope;
+
+use aoc_common::{parse_number, ParseError, Result, Solution};
+use crane::{CrateMover, CrateMover9000, CrateMover9001};
+use std::fmt::{self, Display};
+use std::ops::Range;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Move {
     pub quantity: usize,
     pub from: usize,
@@ -10,107 +20,441 @@ impl Move {
         Move { quantity, from, to }
     }
 
-    pub fn parse(description: &str) -> Self {
-        let mut words = description.split(' ');
+    pub fn parse(description: &str) -> Result<Self> {
+        let words: Vec<_> = description.split(' ').collect();
 
-        let mut get_next_int = || {
-            words
-                .nth(1)
-                .expect("invalid move")
-                .parse()
-                .expect("invalid int")
-        };
+        match words[..] {
+            ["move", quantity, "from", from, "to", to] => {
+                let int = |word| parse_number(Day05::DAY, description, word);
+                Ok(Move::new(int(quantity)?, int(from)?, int(to)?))
+            }
+            _ => Err(ParseError::new(
+                Day05::DAY,
+                description,
+                description,
+                "expected a move like \"move 1 from 2 to 3\"",
+            )),
+        }
+    }
+}
 
-        Move::new(get_next_int(), get_next_int(), get_next_int())
+// like "move 1 from 2 to 3"
+impl Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.quantity, self.from, self.to
+        )
     }
 }
 
-pub fn apply_moves_9000(stacks: &mut [Vec<char>], moves: &Vec<Move>) {
-    for moov in moves {
-        for _ in 0..moov.quantity {
-            let krate = stacks[moov.from - 1].pop().expect("invalid move");
-            stacks[moov.to - 1].push(krate)
+// A move that can't be made on the stacks as they are when it comes up.
+// Moves are numbered from 1, and `heights` are the stacks' heights just
+// before it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MoveError {
+    pub number: usize,
+    pub moov: Move,
+    pub stack: usize,
+    pub heights: Vec<usize>,
+    pub problem: MoveProblem,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MoveProblem {
+    NoSuchStack,
+    NotEnoughCrates,
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Self {
+            number,
+            moov,
+            stack,
+            heights,
+            problem,
+        } = self;
+        write!(f, "move {number} ({moov}): ")?;
+        match problem {
+            MoveProblem::NoSuchStack => {
+                write!(f, "there's no stack {stack}, only 1 to {}", heights.len())?
+            }
+            MoveProblem::NotEnoughCrates => {
+                let height = heights[stack - 1];
+                let crates = if height == 1 { "crate" } else { "crates" };
+                write!(f, "stack {stack} only has {height} {crates}")?
+            }
         }
+        let heights: Vec<String> = heights.iter().map(|h| h.to_string()).collect();
+        write!(f, " (the stacks' heights are {})", heights.join(" "))
     }
 }
 
-pub fn apply_moves_9001(stacks: &mut [Vec<char>], moves: &Vec<Move>) {
-    for moov in moves {
-        let from_len = stacks[moov.from - 1].len();
-
-        // I benchmarked this commented option which allocates an intermediate Vec.
-        // Criterion reported that it was about 300% slower than what I went with.
-        // I mainly wanted to get some experience with benchmarking; I'm not 100%
-        // sure I set everything up correctly, but the results make sense to me.
-        //
-        // let mut crates = stacks[moov.from - 1].split_off(from_len - moov.quantity);
-        // stacks[moov.to - 1].append(&mut crates);
+impl std::error::Error for MoveError {}
 
-        for i in (from_len - moov.quantity)..from_len {
-            stacks[moov.to - 1].push(stacks[moov.from - 1][i]);
+// Checks that the move, the `number`th, can be made on stacks with these
+// heights (given by stack, counting from 0), which are only all worked out
+// if it can't.
+pub fn check_move(
+    moov: &Move,
+    number: usize,
+    num_stacks: usize,
+    height: impl Fn(usize) -> usize,
+) -> std::result::Result<(), MoveError> {
+    let error = |stack, problem| MoveError {
+        number,
+        moov: *moov,
+        stack,
+        heights: (0..num_stacks).map(&height).collect(),
+        problem,
+    };
+    for stack in [moov.from, moov.to] {
+        if !(1..=num_stacks).contains(&stack) {
+            return Err(error(stack, MoveProblem::NoSuchStack));
         }
-        stacks[moov.from - 1].truncate(from_len - moov.quantity);
     }
+    if height(moov.from - 1) < moov.quantity {
+        return Err(error(moov.from, MoveProblem::NotEnoughCrates));
+    }
+    Ok(())
+}
+
+// Checks every move against the stacks without moving any crates, by
+// following just the stacks' heights.
+pub fn check_moves(stacks: &[Vec<String>], moves: &[Move]) -> std::result::Result<(), MoveError> {
+    let mut heights: Vec<usize> = stacks.iter().map(|stack| stack.len()).collect();
+    for (i, moov) in moves.iter().enumerate() {
+        check_move(moov, i + 1, heights.len(), |stack| heights[stack])?;
+        heights[moov.from - 1] -= moov.quantity;
+        heights[moov.to - 1] += moov.quantity;
+    }
+    Ok(())
 }
 
-pub fn top_of_stacks(stacks: &[Vec<char>]) -> String {
-    stacks.iter().filter_map(|stack| stack.last()).collect()
+pub fn apply_moves_9000(
+    stacks: &mut [Vec<String>],
+    moves: &[Move],
+) -> std::result::Result<(), MoveError> {
+    CrateMover9000.apply_moves(stacks, moves)
 }
 
-pub fn parse_input(input: &str) -> (Vec<Vec<char>>, Vec<Move>) {
-    let mut parts = input.trim_end().split("\n\n");
+pub fn apply_moves_9001(
+    stacks: &mut [Vec<String>],
+    moves: &[Move],
+) -> std::result::Result<(), MoveError> {
+    // I benchmarked this commented option which allocates an intermediate Vec.
+    // Criterion reported that it was about 300% slower than what I went with.
+    // I mainly wanted to get some experience with benchmarking; I'm not 100%
+    // sure I set everything up correctly, but the results make sense to me.
+    //
+    // let mut crates = stacks[moov.from - 1].split_off(from_len - moov.quantity);
+    // stacks[moov.to - 1].append(&mut crates);
 
-    (
-        parse_stacks(parts.next().expect("missing stacks descriptions")),
-        parse_moves(parts.next().expect("missing moves descriptions")),
-    )
+    CrateMover9001.apply_moves(stacks, moves)
+}
+
+pub fn top_of_stacks(stacks: &[Vec<String>]) -> String {
+    stacks
+        .iter()
+        .filter_map(|stack| stack.last())
+        .map(String::as_str)
+        .collect()
 }
 
-fn parse_stacks(stacks_descriptions: &str) -> Vec<Vec<char>> {
+pub fn parse_input(input: &str) -> Result<(Vec<Vec<String>>, Vec<Move>)> {
+    let input = input.trim_end();
+    let (stacks_descriptions, move_descriptions) = input.split_once("\n\n").ok_or_else(|| {
+        let message = "expected the stacks and the moves separated by a blank line";
+        ParseError::new(Day05::DAY, input, &input[input.len()..], message)
+    })?;
+
+    Ok((
+        parse_stacks(stacks_descriptions).map_err(|e| e.within(input, stacks_descriptions))?,
+        parse_moves(move_descriptions).map_err(|e| e.within(input, move_descriptions))?,
+    ))
+}
+
+// The drawing of the stacks, like the top of the puzzle's input, which
+// parse_stacks reads back. Every column is as wide as the longest label or
+// the widest stack number, whichever is wider, so that crates with longer
+// labels and stacks past 9 or 99 still line up with their numbers.
+pub fn draw_stacks(stacks: &[Vec<String>]) -> String {
+    let longest = stacks
+        .iter()
+        .flatten()
+        .map(|label| label.chars().count())
+        .max();
+    let widest_number = stacks.len().to_string().len();
+    let width = 2 + longest.unwrap_or(1).max(widest_number);
+    let height = stacks.iter().map(|stack| stack.len()).max().unwrap_or(0);
+
+    let mut lines: Vec<String> = (0..height)
+        .rev()
+        .map(|level| {
+            let crates: Vec<String> = stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(label) => format!("{:width$}", format!("[{label}]")),
+                    None => " ".repeat(width),
+                })
+                .collect();
+            crates.join(" ").trim_end().to_string()
+        })
+        .collect();
+    let numbers: Vec<String> = (1..=stacks.len())
+        .map(|i| format!(" {i:<0$}", width - 1))
+        .collect();
+    lines.push(numbers.join(" ").trim_end().to_string());
+    lines.join("\n")
+}
+
+// The drawing of the stacks, like the top of the puzzle's input. The stack
+// numbers along the bottom say where each stack's column is, and a crate
+// belongs to the stack whose number it's drawn over, so there can be any
+// number of stacks and crates can have labels of any length, like [AB].
+pub fn parse_stacks(stacks_descriptions: &str) -> Result<Vec<Vec<String>>> {
+    let error = |at, message: &str| ParseError::new(Day05::DAY, stacks_descriptions, at, message);
     let mut lines = stacks_descriptions.split('\n').rev();
-    let stack_numbers = lines
-        .next()
-        .expect("invalid stacks description")
-        .trim()
-        .as_bytes();
-
-    // we expect at most 9 stacks
-    let num_stacks = (stack_numbers.last().unwrap() - b'0') as usize;
-    let mut stacks = vec![vec![]; num_stacks];
-
-    for line in lines {
-        let bytes = line.as_bytes();
-        for (i, stack) in stacks.iter_mut().enumerate() {
-            match bytes.get(1 + (i * 4)) {
-                Some(byte) if *byte != b' ' => stack.push(*byte as char),
-                _ => continue,
+    let stack_numbers = lines.next().unwrap_or_default();
+
+    // the columns each stack's number covers
+    let mut columns = vec![];
+    for (span, word) in words(stack_numbers) {
+        let expected = columns.len() + 1;
+        match word.parse::<usize>() {
+            Ok(number) if number == expected => columns.push(span),
+            Ok(_) => return Err(error(word, &format!("expected stack {expected} here"))),
+            Err(_) => return Err(error(word, "expected the stack numbers below the crates")),
+        }
+    }
+    if columns.is_empty() {
+        let message = "expected the stack numbers below the crates";
+        return Err(error(stack_numbers.trim(), message));
+    }
+    let mut stacks = vec![vec![]; columns.len()];
+
+    // the crates, from the bottom up
+    for (level, line) in lines.enumerate() {
+        for (span, word) in words(line) {
+            let label = word
+                .strip_prefix('[')
+                .and_then(|word| word.strip_suffix(']'))
+                .filter(|label| !label.is_empty() && !label.contains(['[', ']']))
+                .ok_or_else(|| error(word, "expected a crate like [A]"))?;
+
+            let over: Vec<usize> = (0..columns.len())
+                .filter(|&i| span.start < columns[i].end && columns[i].start < span.end)
+                .collect();
+            let stack = match over[..] {
+                [stack] => &mut stacks[stack],
+                [] => return Err(error(word, "this crate isn't over any stack's number")),
+                _ => {
+                    let message = "this crate is over more than one stack's number";
+                    return Err(error(word, message));
+                }
+            };
+            if stack.len() > level {
+                let message = "there's already a crate over this stack on this line";
+                return Err(error(word, message));
+            }
+            if stack.len() < level {
+                return Err(error(word, "this crate has nothing under it"));
             }
+            stack.push(label.to_string());
         }
     }
 
-    stacks
+    Ok(stacks)
+}
+
+// the words of a line split at whitespace, with the columns they cover
+fn words(line: &str) -> impl Iterator<Item = (Range<usize>, &str)> {
+    line.split_whitespace().map(move |word| {
+        let offset = word.as_ptr() as usize - line.as_ptr() as usize;
+        let start = line[..offset].chars().count();
+        (start..start + word.chars().count(), word)
+    })
 }
 
-fn parse_moves(move_descriptions: &str) -> Vec<Move> {
-    move_descriptions.split('\n').map(Move::parse).collect()
+fn parse_moves(move_descriptions: &str) -> Result<Vec<Move>> {
+    move_descriptions
+        .split('\n')
+        .map(|line| Move::parse(line).map_err(|e| e.within(move_descriptions, line)))
+        .collect()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: usize = 5;
+
+    type Input<'a> = (Vec<Vec<String>>, Vec<Move>);
+    type Part1 = String;
+    type Part2 = String;
+
+    // Rejects moves that can't be made, pointing at the first of them.
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let (stacks, moves) = parse_input(input)?;
+        if let Err(error) = check_moves(&stacks, &moves) {
+            let input = input.trim_end();
+            let move_descriptions = input.split_once("\n\n").map_or(input, |(_, moves)| moves);
+            let line = move_descriptions
+                .split('\n')
+                .nth(error.number - 1)
+                .unwrap_or(move_descriptions);
+            return Err(ParseError::new(Self::DAY, input, line, error.to_string()));
+        }
+        Ok((stacks, moves))
+    }
+
+    fn part_1((stacks, moves): &(Vec<Vec<String>>, Vec<Move>)) -> String {
+        let mut stacks = stacks.clone();
+        apply_moves_9000(&mut stacks, moves).expect("the moves were checked when parsing");
+        top_of_stacks(&stacks)
+    }
+
+    fn part_2((stacks, moves): &(Vec<Vec<String>>, Vec<Move>)) -> String {
+        let mut stacks = stacks.clone();
+        apply_moves_9001(&mut stacks, moves).expect("the moves were checked when parsing");
+        top_of_stacks(&stacks)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::Rng;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
     use std::fs;
 
     #[test]
     fn new_move() {
-        assert_eq!(Move::parse("move 2 from 1 to 3"), Move::new(2, 1, 3));
+        assert_eq!(Move::parse("move 2 from 1 to 3"), Ok(Move::new(2, 1, 3)));
+    }
+
+    #[test]
+    fn invalid_move() {
+        let error = Move::parse("move 2 from one to 3").unwrap_err();
+        assert_eq!((error.column, error.snippet.as_str()), (13, "one"));
+
+        let error = Move::parse("move 2 to 3").unwrap_err();
+        assert_eq!((error.column, error.snippet.as_str()), (1, "move 2 to 3"));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let input = "[A]\n 1\n\nmove 1 from 1 to 1\nmove 1 from 1 to x\n";
+        let error = parse_input(input).unwrap_err();
+        assert_eq!((error.line, error.column), (5, 18));
+
+        let error = parse_input("[A] [B]\n 1   2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 7));
+
+        let error = parse_input("[A] [B]\n\nmove 1 from 1 to 2").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+    }
+
+    #[test]
+    fn invalid_drawing() {
+        let error = |drawing| {
+            let error = parse_stacks(drawing).unwrap_err();
+            (error.line, error.column, error.message)
+        };
+        assert_eq!(
+            error("[A] [B]\n 1   3"),
+            (2, 6, String::from("expected stack 2 here"))
+        );
+        assert_eq!(
+            error("[A] [B] [C]\n 1   2"),
+            (
+                1,
+                9,
+                String::from("this crate isn't over any stack's number")
+            )
+        );
+        assert_eq!(
+            error("[LONG]\n 1 2"),
+            (
+                1,
+                1,
+                String::from("this crate is over more than one stack's number")
+            )
+        );
+        assert_eq!(
+            error("    [B]\n[A]\n 1   2"),
+            (1, 5, String::from("this crate has nothing under it"))
+        );
+        assert_eq!(
+            error("[A] B\n 1   2"),
+            (1, 5, String::from("expected a crate like [A]"))
+        );
+        assert_eq!(
+            error("[A][B]\n 1   2"),
+            (1, 1, String::from("expected a crate like [A]"))
+        );
+    }
+
+    #[test]
+    fn wide_drawings() {
+        let drawing = "[AB]      [X]\n[CD] [E]  [Y] [Z]\n 1   2    3   4   5   6   7   8   9   10";
+        let stacks = parse_stacks(drawing).unwrap();
+        assert_eq!(stacks.len(), 10);
+        assert_eq!(stacks[0], ["CD", "AB"]);
+        assert_eq!(stacks[1], ["E"]);
+        assert_eq!(stacks[2], ["Y", "X"]);
+        assert_eq!(stacks[3], ["Z"]);
+        assert!(stacks[4..].iter().all(|stack| stack.is_empty()));
+
+        // three-digit stack numbers widen every column
+        let stacks: Vec<Vec<String>> = (0..120).map(|_| vec![String::from("A")]).collect();
+        let drawing = draw_stacks(&stacks);
+        assert!(drawing.starts_with("[A]   [A]"));
+        assert_eq!(parse_stacks(&drawing), Ok(stacks));
+    }
+
+    #[test]
+    fn invalid_moves() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let (mut stacks, mut moves) = parse_input(&input).unwrap();
+        assert_eq!(check_moves(&stacks, &moves), Ok(()));
+
+        // the third move takes three crates from stack 2, which has two by then
+        moves[2] = Move::new(3, 2, 3);
+        let error = check_moves(&stacks, &moves).unwrap_err();
+        assert_eq!((error.number, error.stack), (3, 2));
+        assert_eq!(error.problem, MoveProblem::NotEnoughCrates);
+        assert_eq!(error.heights, [0, 2, 4]);
+        assert_eq!(
+            error.to_string(),
+            "move 3 (move 3 from 2 to 3): stack 2 only has 2 crates (the stacks' heights are 0 2 4)"
+        );
+        // applying them stops there, with the first two moves made
+        assert_eq!(apply_moves_9001(&mut stacks, &moves), Err(error));
+        assert_eq!(
+            stacks.iter().map(|s| s.len()).collect::<Vec<_>>(),
+            [0, 2, 4]
+        );
+
+        let moves = [Move::new(1, 0, 1)];
+        let error = check_moves(&stacks, &moves).unwrap_err();
+        assert_eq!((error.stack, error.problem), (0, MoveProblem::NoSuchStack));
+        let moves = [Move::new(1, 1, 4)];
+        let error = check_moves(&stacks, &moves).unwrap_err();
+        assert_eq!((error.stack, error.problem), (4, MoveProblem::NoSuchStack));
+
+        let error = Day05::parse("[A]\n 1\n\nmove 1 from 1 to 1\nmove 2 from 1 to 1").unwrap_err();
+        assert_eq!((error.line, error.column), (5, 1));
     }
 
     #[test]
     fn test_parse_input() {
         let input = fs::read_to_string("test-input.txt").expect("failed to read input");
-        let (stacks, moves) = parse_input(&input);
+        let (stacks, moves) = parse_input(&input).unwrap();
 
-        let expected_stacks = vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
+        let expected_stacks = vec![vec!["Z", "N"], vec!["M", "C", "D"], vec!["P"]];
         let expected_moves = vec![
             Move::new(1, 2, 1),
             Move::new(3, 1, 3),
@@ -125,16 +469,43 @@ mod tests {
     #[test]
     fn crate_mover_9000() {
         let input = fs::read_to_string("test-input.txt").expect("failed to read input");
-        let (mut stacks, moves) = parse_input(&input);
-        apply_moves_9000(&mut stacks, &moves);
+        let (mut stacks, moves) = parse_input(&input).unwrap();
+        apply_moves_9000(&mut stacks, &moves).unwrap();
         assert_eq!(top_of_stacks(&stacks), "CMZ");
     }
 
     #[test]
     fn crate_mover_9001() {
         let input = fs::read_to_string("test-input.txt").expect("failed to read input");
-        let (mut stacks, moves) = parse_input(&input);
-        apply_moves_9001(&mut stacks, &moves);
+        let (mut stacks, moves) = parse_input(&input).unwrap();
+        apply_moves_9001(&mut stacks, &moves).unwrap();
         assert_eq!(top_of_stacks(&stacks), "MCD");
     }
+
+    fn sorted_crates(stacks: &[Vec<String>]) -> Vec<String> {
+        let mut crates: Vec<String> = stacks.concat();
+        crates.sort_unstable();
+        crates
+    }
+
+    proptest! {
+        #[test]
+        fn stacks_round_trip(stacks in vec(vec("[A-Z]{1,3}", 0..8), 1..=150)) {
+            prop_assert_eq!(parse_stacks(&draw_stacks(&stacks)), Ok(stacks));
+        }
+
+        #[test]
+        fn cranes_keep_every_crate(seed in any::<u64>(), size in 1..50usize) {
+            let input = generator::generate(&mut Rng::new(seed), size);
+            let (stacks, moves) = parse_input(&input).unwrap();
+
+            let mut stacks_9000 = stacks.clone();
+            apply_moves_9000(&mut stacks_9000, &moves).unwrap();
+            let mut stacks_9001 = stacks.clone();
+            apply_moves_9001(&mut stacks_9001, &moves).unwrap();
+
+            prop_assert_eq!(sorted_crates(&stacks_9000), sorted_crates(&stacks));
+            prop_assert_eq!(sorted_crates(&stacks_9001), sorted_crates(&stacks));
+        }
+    }
 }
@@ -1,29 +1,229 @@
 // Advent of Code 2022: Day 5
 // https://adventofcode.com/2022/day/5
-// Usage: `cargo run <input-file>`
+// Usage: `cargo run <input-file|directory|->... [--format text|json] [--check | [--crane MODEL] [--frames | --step N]]`
+//
+// With `--crane` the moves are applied with just that model of crane rather
+// than the two the puzzle asks about: 9000 or 9001, capacity:N for a crane
+// that lifts at most N crates at a time, alternating:N for one that also
+// turns every other batch over, or custom:PATTERN for one that lifts batches
+// following a pattern like 2r,1 (two crates turned over, then one, and so on).
+//
+// `--check` instead makes sure every move can be made, following just the
+// stacks' heights, and reports the first that can't: which move it is, the
+// stack it can't use and how tall the stacks are by then.
+//
+// `--frames` draws the stacks after every move, starting with how they were,
+// and `--step N` draws them after just the first N moves, both with the
+// crane `--crane` names (CrateMover 9000 if it doesn't).
 
-use std::env;
-use std::fs;
+use aoc_common::{for_each_input, solve_inputs, Args, Format};
+use day_05::crane::{crane, CrateMover, CrateMover9000};
+use day_05::history::History;
+use day_05::{check_moves, parse_input, top_of_stacks, Day05, MoveError, MoveProblem};
+use serde_json::json;
+use std::process;
 
-use day_05::{apply_moves_9000, apply_moves_9001, parse_input, top_of_stacks};
+const FLAGS: [&str; 4] = ["--crane MODEL", "--frames", "--step N", "--check"];
 
 fn main() {
-    let input_filename = env::args().nth(1).expect("please supply an input filename");
-    let input = fs::read_to_string(&input_filename).expect("failed to read input file");
+    let args = Args::from_env_with(&FLAGS);
+    check_flags(&args).unwrap_or_else(|message| {
+        eprintln!("{message}");
+        process::exit(2);
+    });
 
-    let (mut stacks_part_1, moves) = parse_input(&input);
-    let mut stacks_part_2 = stacks_part_1.clone();
+    if args.has("--check") {
+        print_checked(&args);
+        return;
+    }
 
-    apply_moves_9000(&mut stacks_part_1, &moves);
-    apply_moves_9001(&mut stacks_part_2, &moves);
+    let crane = args.value::<String>("--crane").map(|model| {
+        crane(&model).unwrap_or_else(|error| {
+            eprintln!("{error}");
+            process::exit(2);
+        })
+    });
+    let step = args.value::<usize>("--step");
+    if args.has("--frames") || step.is_some() {
+        print_frames(&args, crane.unwrap_or(Box::new(CrateMover9000)), step);
+        return;
+    }
+    if let Some(crane) = crane {
+        print_with_crane(&args, crane);
+        return;
+    }
 
-    println!(
-        "Appying the moves with CrateMover 9000 we get: {}",
-        top_of_stacks(&stacks_part_1)
-    );
+    solve_inputs::<Day05>(&args, |report| {
+        println!(
+            "Appying the moves with CrateMover 9000 we get: {}",
+            report.part_1
+        );
+        println!(
+            "Appying the moves with CrateMover 9001 we get: {}",
+            report.part_2
+        );
+    });
+}
+
+// Checking follows just the stacks' heights, so it can't be combined with
+// anything that moves crates, and a single step is drawn instead of every
+// frame rather than as well as them.
+fn check_flags(args: &Args) -> Result<(), String> {
+    for flag in ["--crane", "--frames", "--step"] {
+        args.at_most_one_of(&["--check", flag])?;
+    }
+    args.at_most_one_of(&["--frames", "--step"])
+}
+
+fn print_with_crane(args: &Args, mut crane: Box<dyn CrateMover>) {
+    for_each_input(args, |source, input| {
+        let (mut stacks, moves) = parse_input(input).map_err(|error| error.to_string())?;
+        crane
+            .apply_moves(&mut stacks, &moves)
+            .map_err(|error| format!("{source}: {error}"))?;
+        let top = top_of_stacks(&stacks);
+
+        match args.format {
+            Format::Json => {
+                let report = json!({
+                    "day": 5,
+                    "input": source.to_string(),
+                    "crane": crane.name(),
+                    "top": top,
+                });
+                println!("{report}");
+            }
+            Format::Text => {
+                println!("Applying the moves with {} we get: {top}", crane.name());
+            }
+        }
+        Ok(())
+    });
+}
+
+fn print_checked(args: &Args) {
+    for_each_input(args, |source, input| {
+        let (stacks, moves) = parse_input(input).map_err(|error| error.to_string())?;
+        let checked = check_moves(&stacks, &moves);
+
+        match args.format {
+            Format::Json => {
+                let error = checked.as_ref().err().map(|error| {
+                    let MoveError {
+                        number,
+                        stack,
+                        heights,
+                        problem,
+                        ..
+                    } = error;
+                    let problem = match problem {
+                        MoveProblem::NoSuchStack => "no such stack",
+                        MoveProblem::NotEnoughCrates => "not enough crates",
+                    };
+                    json!({
+                        "move": number,
+                        "stack": stack,
+                        "heights": heights,
+                        "problem": problem,
+                        "message": error.to_string(),
+                    })
+                });
+                let report = json!({
+                    "day": 5,
+                    "input": source.to_string(),
+                    "moves": moves.len(),
+                    "valid": checked.is_ok(),
+                    "error": error,
+                });
+                println!("{report}");
+            }
+            Format::Text => match &checked {
+                Ok(()) => println!("All {} moves can be made", moves.len()),
+                Err(error) => println!("Invalid {error}"),
+            },
+        }
+        checked.map_err(|error| format!("{source}: {error}"))
+    });
+}
+
+// Draws the stacks after the moves up to `step`, or after every move with
+// no step.
+fn print_frames(args: &Args, mut crane: Box<dyn CrateMover>, step: Option<usize>) {
+    for_each_input(args, |source, input| {
+        let (stacks, moves) = parse_input(input).map_err(|error| error.to_string())?;
+        let mut history = History::new(stacks);
+        // the frames that could be made are still worth drawing, and a move
+        // that couldn't is why there are fewer steps than moves
+        let applied = history
+            .apply_all(&mut *crane, &moves)
+            .map_err(|error| format!("{source}: {error}"));
+        if step.is_some_and(|step| step > history.len()) {
+            applied?;
+            return Err(format!("{source}: there are only {} steps", history.len()));
+        }
+        let steps = match step {
+            Some(step) => step..=step,
+            None => 0..=history.len(),
+        };
+
+        let frames: Vec<(usize, Option<String>, String)> = steps
+            .map(|step| {
+                history.jump_to(step);
+                let moov = history.move_at(step).map(|moov| moov.to_string());
+                (step, moov, history.draw())
+            })
+            .collect();
+
+        match args.format {
+            Format::Json => {
+                let frames: Vec<_> = frames
+                    .iter()
+                    .map(|(step, moov, drawing)| json!({"step": step, "move": moov, "drawing": drawing}))
+                    .collect();
+                let report = json!({
+                    "day": 5,
+                    "input": source.to_string(),
+                    "crane": crane.name(),
+                    "frames": frames,
+                });
+                println!("{report}");
+            }
+            Format::Text => {
+                for (i, (step, moov, drawing)) in frames.iter().enumerate() {
+                    let gap = if i > 0 { "\n" } else { "" };
+                    match moov {
+                        Some(moov) => println!("{gap}Step {step} ({moov}):"),
+                        None => println!("{gap}Step {step} (the start):"),
+                    }
+                    println!("{drawing}");
+                }
+            }
+        }
+        applied
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_flags_of(args: &[&str]) -> Result<(), String> {
+        let args = Args::parse_with(args.iter().map(|arg| arg.to_string()), &FLAGS).unwrap();
+        check_flags(&args)
+    }
+
+    #[test]
+    fn test_check_flags() {
+        assert_eq!(check_flags_of(&["input.txt", "--check"]), Ok(()));
+        assert_eq!(
+            check_flags_of(&["input.txt", "--crane", "9001", "--frames"]),
+            Ok(())
+        );
+        assert_eq!(check_flags_of(&["input.txt", "--step", "2"]), Ok(()));
 
-    println!(
-        "Appying the moves with CrateMover 9001 we get: {}",
-        top_of_stacks(&stacks_part_2)
-    );
+        assert!(check_flags_of(&["input.txt", "--check", "--crane", "9001"]).is_err());
+        assert!(check_flags_of(&["input.txt", "--check", "--frames"]).is_err());
+        assert!(check_flags_of(&["input.txt", "--check", "--step", "2"]).is_err());
+        assert!(check_flags_of(&["input.txt", "--frames", "--step", "2"]).is_err());
+    }
 }
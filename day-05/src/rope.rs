@@ -0,0 +1,288 @@
+use crate::{check_move, Move, MoveError};
+use aoc_common::Rng;
+
+// Stacks kept as treaps (binary trees ordered by position, balanced by
+// random priorities) rather than Vecs, so that a move of any size splits
+// the top off one tree and merges it onto another in O(log n), instead of
+// moving its crates one by one. A node's `reversed` flag says its subtree
+// should be read backwards, which is how the CrateMover 9000 turns a move
+// over without touching each crate; flags are pushed down to the children
+// when a node is next split or merged.
+#[derive(Debug, Clone)]
+pub struct RopeStacks {
+    nodes: Vec<Node>,
+    labels: Vec<String>,
+    roots: Vec<usize>,
+    rng: Rng,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    left: usize,
+    right: usize,
+    size: usize,
+    priority: u64,
+    reversed: bool,
+    // an index into the labels
+    label: usize,
+}
+
+// no node, for empty trees and missing children
+const NIL: usize = usize::MAX;
+
+impl RopeStacks {
+    pub fn new(stacks: &[Vec<String>]) -> Self {
+        let mut rope = Self {
+            nodes: vec![],
+            labels: vec![],
+            roots: vec![],
+            rng: Rng::new(0),
+        };
+        for stack in stacks {
+            let mut root = NIL;
+            for label in stack {
+                let node = rope.leaf(label);
+                root = rope.merge(root, node);
+            }
+            rope.roots.push(root);
+        }
+        rope
+    }
+
+    pub fn height(&self, stack: usize) -> usize {
+        self.size(self.roots[stack])
+    }
+
+    pub fn apply_moves_9000(&mut self, moves: &[Move]) -> Result<(), MoveError> {
+        self.apply_moves(moves, true)
+    }
+
+    pub fn apply_moves_9001(&mut self, moves: &[Move]) -> Result<(), MoveError> {
+        self.apply_moves(moves, false)
+    }
+
+    // Applies the moves in order, stopping at the first that can't be made,
+    // with each move's crates turned over or not.
+    fn apply_moves(&mut self, moves: &[Move], reversed: bool) -> Result<(), MoveError> {
+        for (i, moov) in moves.iter().enumerate() {
+            check_move(moov, i + 1, self.roots.len(), |stack| self.height(stack))?;
+            if moov.from == moov.to {
+                continue;
+            }
+            let (from, to) = (moov.from - 1, moov.to - 1);
+            let (rest, top) = self.split(self.roots[from], self.height(from) - moov.quantity);
+            if reversed && top != NIL {
+                self.nodes[top].reversed ^= true;
+            }
+            self.roots[from] = rest;
+            self.roots[to] = self.merge(self.roots[to], top);
+        }
+        Ok(())
+    }
+
+    pub fn top_of_stacks(&self) -> String {
+        self.roots
+            .iter()
+            .filter(|&&root| root != NIL)
+            .map(|&root| {
+                // follow the last crate down, reading children backwards
+                // under an odd number of flags
+                let (mut node, mut reversed) = (root, false);
+                loop {
+                    reversed ^= self.nodes[node].reversed;
+                    let last = match reversed {
+                        true => self.nodes[node].left,
+                        false => self.nodes[node].right,
+                    };
+                    if last == NIL {
+                        break self.labels[self.nodes[node].label].as_str();
+                    }
+                    node = last;
+                }
+            })
+            .collect()
+    }
+
+    // the stacks as Vecs, from the bottom up
+    pub fn to_stacks(&self) -> Vec<Vec<String>> {
+        self.roots
+            .iter()
+            .map(|&root| {
+                let mut stack = Vec::with_capacity(self.size(root));
+                self.collect(root, false, &mut stack);
+                stack
+            })
+            .collect()
+    }
+
+    fn collect(&self, node: usize, reversed: bool, stack: &mut Vec<String>) {
+        if node == NIL {
+            return;
+        }
+        let Node {
+            left,
+            right,
+            reversed: flipped,
+            label,
+            ..
+        } = self.nodes[node];
+        let reversed = reversed ^ flipped;
+        let (first, second) = if reversed {
+            (right, left)
+        } else {
+            (left, right)
+        };
+        self.collect(first, reversed, stack);
+        stack.push(self.labels[label].clone());
+        self.collect(second, reversed, stack);
+    }
+
+    fn leaf(&mut self, label: &str) -> usize {
+        self.labels.push(label.to_string());
+        self.nodes.push(Node {
+            left: NIL,
+            right: NIL,
+            size: 1,
+            priority: self.rng.next_u64(),
+            reversed: false,
+            label: self.labels.len() - 1,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn size(&self, node: usize) -> usize {
+        if node == NIL {
+            0
+        } else {
+            self.nodes[node].size
+        }
+    }
+
+    fn update(&mut self, node: usize) {
+        let Node { left, right, .. } = self.nodes[node];
+        self.nodes[node].size = 1 + self.size(left) + self.size(right);
+    }
+
+    // passes a node's flag down to its children, swapping them over
+    fn push_down(&mut self, node: usize) {
+        if !self.nodes[node].reversed {
+            return;
+        }
+        let Node { left, right, .. } = self.nodes[node];
+        for child in [left, right] {
+            if child != NIL {
+                self.nodes[child].reversed ^= true;
+            }
+        }
+        let node = &mut self.nodes[node];
+        (node.left, node.right) = (right, left);
+        node.reversed = false;
+    }
+
+    // splits off the first `at` crates of the tree from the rest
+    fn split(&mut self, node: usize, at: usize) -> (usize, usize) {
+        if node == NIL {
+            return (NIL, NIL);
+        }
+        self.push_down(node);
+        let Node { left, right, .. } = self.nodes[node];
+        if self.size(left) >= at {
+            let (first, rest) = self.split(left, at);
+            self.nodes[node].left = rest;
+            self.update(node);
+            (first, node)
+        } else {
+            let (first, rest) = self.split(right, at - self.size(left) - 1);
+            self.nodes[node].right = first;
+            self.update(node);
+            (node, rest)
+        }
+    }
+
+    // the tree with the crates of `bottom` and then those of `top`
+    fn merge(&mut self, bottom: usize, top: usize) -> usize {
+        if bottom == NIL {
+            return top;
+        }
+        if top == NIL {
+            return bottom;
+        }
+        if self.nodes[bottom].priority > self.nodes[top].priority {
+            self.push_down(bottom);
+            let right = self.nodes[bottom].right;
+            self.nodes[bottom].right = self.merge(right, top);
+            self.update(bottom);
+            bottom
+        } else {
+            self.push_down(top);
+            let left = self.nodes[top].left;
+            self.nodes[top].left = self.merge(bottom, left);
+            self.update(top);
+            top
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{apply_moves_9000, apply_moves_9001, generator, parse_input, top_of_stacks};
+    use aoc_common::Rng;
+    use proptest::prelude::*;
+    use std::fs;
+
+    #[test]
+    fn test_rope_stacks() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input");
+        let (stacks, moves) = parse_input(&input).unwrap();
+
+        let mut rope = RopeStacks::new(&stacks);
+        assert_eq!(rope.to_stacks(), stacks);
+        rope.apply_moves_9000(&moves).unwrap();
+        assert_eq!(rope.top_of_stacks(), "CMZ");
+
+        let mut rope = RopeStacks::new(&stacks);
+        rope.apply_moves_9001(&moves).unwrap();
+        assert_eq!(rope.top_of_stacks(), "MCD");
+
+        let error = rope.apply_moves_9001(&[Move::new(2, 1, 2)]).unwrap_err();
+        assert_eq!(error.heights, [1, 1, 4]);
+    }
+
+    #[test]
+    fn huge_moves() {
+        // moving a stack of a million crates back and forth stays quick
+        let tall: Vec<String> = (0..1_000_000).map(|i| i.to_string()).collect();
+        let mut rope = RopeStacks::new(&[tall, vec![]]);
+        let moves: Vec<Move> = (0..10_000)
+            .map(|i| match i % 2 {
+                0 => Move::new(1_000_000 - i, 1, 2),
+                _ => Move::new(1_000_000 - i, 2, 1),
+            })
+            .collect();
+        rope.apply_moves_9000(&moves).unwrap();
+        assert_eq!(rope.height(0) + rope.height(1), 1_000_000);
+    }
+
+    proptest! {
+        #[test]
+        fn ropes_match_vecs(seed in any::<u64>(), size in 1..200usize) {
+            let input = generator::generate(&mut Rng::new(seed), size);
+            let (stacks, moves) = parse_input(&input).unwrap();
+
+            let mut expected = stacks.clone();
+            apply_moves_9000(&mut expected, &moves).unwrap();
+            let mut rope = RopeStacks::new(&stacks);
+            rope.apply_moves_9000(&moves).unwrap();
+            prop_assert_eq!(rope.top_of_stacks(), top_of_stacks(&expected));
+            prop_assert_eq!(rope.to_stacks(), expected);
+
+            let mut expected = stacks.clone();
+            apply_moves_9001(&mut expected, &moves).unwrap();
+            let mut rope = RopeStacks::new(&stacks);
+            rope.apply_moves_9001(&moves).unwrap();
+            prop_assert_eq!(rope.top_of_stacks(), top_of_stacks(&expected));
+            prop_assert_eq!(rope.to_stacks(), expected);
+        }
+    }
+}
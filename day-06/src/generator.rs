@@ -0,0 +1,48 @@
+// Generates inputs in the puzzle's format: a datastream of lowercase letters
+// with a start-of-packet marker (4 different letters) and later a
+// start-of-message marker (14 different letters). `size` is the length of
+// the datastream.
+use aoc_common::Rng;
+
+const LETTERS: &[u8; 26] = b"abcdefghijklmnopqrstuvwxyz";
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let len = size.max(40);
+    let packet_start = rng.range(0..=len / 3);
+    let message_start = rng.range(packet_start + 4..=len * 2 / 3);
+    let mut stream = vec![];
+
+    // too few letters to hold a marker until we add one
+    stream.extend((0..packet_start).map(|_| *rng.choose(&LETTERS[..3])));
+    stream.extend(distinct_letters(rng, 4));
+    stream.extend((stream.len()..message_start).map(|_| *rng.choose(&LETTERS[..13])));
+    stream.extend(distinct_letters(rng, 14));
+    stream.extend((stream.len()..len).map(|_| *rng.choose(LETTERS)));
+
+    String::from_utf8(stream).unwrap() + "\n"
+}
+
+fn distinct_letters(rng: &mut Rng, n: usize) -> Vec<u8> {
+    let mut letters = LETTERS.to_vec();
+    rng.shuffle(&mut letters);
+    letters.truncate(n);
+    letters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day06;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_have_markers() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 200);
+            let datastream = Day06::parse(&input).unwrap();
+            assert_eq!(datastream.len(), 200);
+            assert!(Day06::part_1(&datastream).0.is_some());
+            assert!(Day06::part_2(&datastream).0.is_some());
+        }
+    }
+}
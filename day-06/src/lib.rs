@@ -1,3 +1,6 @@
+pub mod generator;
+
+use aoc_common::{Maybe, ParseError, Result, Solution};
 use std::collections::HashSet;
 
 pub fn index_after_n_unique_characters(datastream: &[u8], n: usize) -> Option<usize> {
@@ -24,9 +27,45 @@ pub fn index_after_n_unique_characters_hashset(datastream: &[u8], n: usize) -> O
     None
 }
 
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: usize = 6;
+
+    type Input<'a> = &'a [u8];
+    type Part1 = Maybe<usize>;
+    type Part2 = Maybe<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let datastream = input.trim();
+        match datastream
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_lowercase())
+        {
+            Some((i, c)) => {
+                let at = &datastream[i..i + c.len_utf8()];
+                let message = format!("expected only the letters a-z, got {c:?}");
+                Err(ParseError::new(Day06::DAY, input, at, message))
+            }
+            None => Ok(datastream.as_bytes()),
+        }
+    }
+
+    // start-of-packet marker
+    fn part_1(datastream: &&[u8]) -> Maybe<usize> {
+        index_after_n_unique_characters(datastream, 4).into()
+    }
+
+    // start-of-message marker
+    fn part_2(datastream: &&[u8]) -> Maybe<usize> {
+        index_after_n_unique_characters(datastream, 14).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_index_after_start_of_packet() {
@@ -71,4 +110,28 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Day06::parse("abc\n"), Ok("abc".as_bytes()));
+
+        let error = Day06::parse("abcD").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+    }
+
+    proptest! {
+        #[test]
+        fn markers_are_the_first_unique_run(datastream in "[a-h]{0,40}", n in 1..=8usize) {
+            let datastream = datastream.as_bytes();
+            let index = index_after_n_unique_characters(datastream, n);
+            prop_assert_eq!(index, index_after_n_unique_characters_hashset(datastream, n));
+
+            let unique = |i: usize| {
+                let run = &datastream[i - n..i];
+                run.iter().all(|c| run.iter().filter(|other| *other == c).count() == 1)
+            };
+            let first_unique = (n..=datastream.len()).find(|&i| unique(i));
+            prop_assert_eq!(index, first_unique);
+        }
+    }
 }
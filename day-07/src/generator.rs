@@ -0,0 +1,133 @@
+// Generates inputs in the puzzle's format: the terminal output of exploring
+// a random directory tree with `$ cd` and `$ ls`. The files add up to between
+// 40,000,000 and 70,000,000 bytes, so that the disk has room for everything
+// but not for the update. `size` is the number of directories.
+use aoc_common::Rng;
+
+const DISK_SPACE: usize = 70_000_000;
+const NEEDED_SPACE: usize = 30_000_000;
+
+struct Directory {
+    files: Vec<(String, usize)>,
+    children: Vec<(String, Directory)>,
+}
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // grow a tree by adding each directory under a random existing one,
+    // addressed by the path of child indices from the root
+    let mut root = Directory::new(rng);
+    let mut paths: Vec<Vec<usize>> = vec![vec![]];
+    for _ in 1..size.max(1) {
+        let mut path = rng.choose(&paths).clone();
+        let parent = root.at(&path);
+        let name = unique_name(rng, parent);
+        parent.children.push((name, Directory::new(rng)));
+        path.push(parent.children.len() - 1);
+        paths.push(path);
+    }
+
+    // shrink the files if there are too many of them, then top the disk up
+    // with one big file so the update has to free up some space
+    let used = rng.range(DISK_SPACE - NEEDED_SPACE..=DISK_SPACE - 1_000_000);
+    let total = root.total_size();
+    if total > used / 2 {
+        root.scale_files(used / 2, total);
+    }
+    let big_file = used - root.total_size();
+    let path = rng.choose(&paths).clone();
+    let directory = root.at(&path);
+    let name = unique_name(rng, directory) + ".dat";
+    directory.files.push((name, big_file));
+
+    let mut output = String::new();
+    root.write("/", &mut output);
+    output
+}
+
+impl Directory {
+    fn new(rng: &mut Rng) -> Self {
+        let mut directory = Self {
+            files: vec![],
+            children: vec![],
+        };
+        for _ in 0..rng.range(0..=4) {
+            let name = unique_name(rng, &directory) + *rng.choose(&["", ".txt", ".log", ".bin"]);
+            directory.files.push((name, rng.range(1000..=300_000)));
+        }
+        directory
+    }
+
+    fn at(&mut self, path: &[usize]) -> &mut Self {
+        match path.split_first() {
+            None => self,
+            Some((&i, rest)) => self.children[i].1.at(rest),
+        }
+    }
+
+    fn total_size(&self) -> usize {
+        let files: usize = self.files.iter().map(|(_, size)| size).sum();
+        let children: usize = self.children.iter().map(|(_, dir)| dir.total_size()).sum();
+        files + children
+    }
+
+    fn scale_files(&mut self, target: usize, total: usize) {
+        for (_, size) in self.files.iter_mut() {
+            *size = (*size * target / total).max(1);
+        }
+        for (_, child) in self.children.iter_mut() {
+            child.scale_files(target, total);
+        }
+    }
+
+    fn write(&self, name: &str, output: &mut String) {
+        *output += &format!("$ cd {name}\n$ ls\n");
+        for (name, _) in &self.children {
+            *output += &format!("dir {name}\n");
+        }
+        for (name, size) in &self.files {
+            *output += &format!("{size} {name}\n");
+        }
+        for (name, child) in &self.children {
+            child.write(name, output);
+        }
+        if name != "/" {
+            *output += "$ cd ..\n";
+        }
+    }
+}
+
+// a name that isn't already used in the directory
+fn unique_name(rng: &mut Rng, directory: &Directory) -> String {
+    loop {
+        let len = rng.range(1..=8);
+        let name: String = (0..len)
+            .map(|_| (b'a' + rng.below(26) as u8) as char)
+            .collect();
+        let taken = directory
+            .files
+            .iter()
+            .any(|(file, _)| file.starts_with(&name))
+            || directory.children.iter().any(|(dir, _)| *dir == name);
+        if !taken {
+            return name;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day07;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 50);
+            let directories = Day07::parse(&input).unwrap();
+            assert_eq!(directories.len(), 50);
+            Day07::part_1(&directories);
+            Day07::part_2(&directories);
+        }
+    }
+}
@@ -1,3 +1,6 @@
+pub mod generator;
+
+use aoc_common::{parse_number, ParseError, Result, Solution};
 use std::cell::Cell;
 use std::rc::Rc;
 
@@ -16,42 +19,55 @@ impl<'a> Directory<'a> {
     }
 }
 
-pub fn build_directories(input: &str) -> Vec<Rc<Directory>> {
+pub fn build_directories(input: &str) -> Result<Vec<Rc<Directory<'_>>>> {
     let mut directories: Vec<Rc<Directory>> = vec![];
     let mut directory_stack: Vec<Rc<Directory>> = vec![];
 
     for line in input.trim().split('\n') {
-        match line.as_bytes()[0] {
-            b'$' => {
-                if line.starts_with("$ cd") {
-                    let dir_name = line.split(' ').last().unwrap();
-                    if dir_name == ".." {
-                        directory_stack.pop();
-                        continue;
-                    }
-
-                    let directory = Rc::new(Directory::new(dir_name));
-                    directories.push(Rc::clone(&directory));
-                    directory_stack.push(directory);
+        let mut words = line.split(' ');
+        match (words.next(), words.next(), words.next(), words.next()) {
+            (Some("$"), Some("cd"), Some(".."), None) => {
+                if directory_stack.pop().is_none() {
+                    let message = "can't leave the outermost directory";
+                    return Err(ParseError::new(Day07::DAY, input, line, message));
                 }
             }
-            b'd' => continue,
-            _ => {
-                let size: usize = line
-                    .split(' ')
-                    .next()
-                    .expect("invalid file listing")
-                    .parse()
-                    .expect("invalid file size");
+            (Some("$"), Some("cd"), Some(dir_name), None) => {
+                let directory = Rc::new(Directory::new(dir_name));
+                directories.push(Rc::clone(&directory));
+                directory_stack.push(directory);
+            }
+            (Some("$"), Some("ls"), None, None) => continue,
+            (Some("dir"), Some(_), None, None) => continue,
+            (Some(size), Some(_), None, None) => {
+                let size_str = size;
+                let size: usize = parse_number(Day07::DAY, input, size_str)?;
+                if directory_stack.is_empty() {
+                    let message = "found a file before changing into any directory";
+                    return Err(ParseError::new(Day07::DAY, input, line, message));
+                }
 
                 for directory in &directory_stack {
-                    directory.size.set(directory.size.get() + size);
+                    let total = directory.size.get().checked_add(size).ok_or_else(|| {
+                        let message = "the directory's files add up to more than we can count";
+                        ParseError::new(Day07::DAY, input, size_str, message)
+                    })?;
+                    directory.size.set(total);
                 }
             }
+            _ => {
+                let message = "expected a `cd` or `ls` command, a directory or a file listing";
+                return Err(ParseError::new(Day07::DAY, input, line, message));
+            }
         }
     }
 
-    directories
+    if directories.is_empty() {
+        let message = "expected at least one `cd` into a directory";
+        return Err(ParseError::new(Day07::DAY, input, input.trim(), message));
+    }
+
+    Ok(directories)
 }
 
 // Computes the sum of the sizes of the directories that contain at least 100,000 bytes.
@@ -73,6 +89,28 @@ pub fn part_2(directories: &[Rc<Directory>]) -> usize {
         })
 }
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: usize = 7;
+
+    type Input<'a> = Vec<Rc<Directory<'a>>>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        build_directories(input)
+    }
+
+    fn part_1(directories: &Vec<Rc<Directory>>) -> usize {
+        part_1(directories)
+    }
+
+    fn part_2(directories: &Vec<Rc<Directory>>) -> usize {
+        part_2(directories)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,7 +128,7 @@ mod tests {
     #[test]
     fn test_build_directories() {
         let input = fs::read_to_string("test-input.txt").expect("unable to read test input");
-        let directories = build_directories(&input);
+        let directories = build_directories(&input).unwrap();
 
         let expected_directories = vec![
             directory!["/", 48381165],
@@ -105,14 +143,28 @@ mod tests {
     #[test]
     fn test_part_1() {
         let input = fs::read_to_string("test-input.txt").expect("unable to read test input");
-        let directories = build_directories(&input);
+        let directories = build_directories(&input).unwrap();
         assert_eq!(part_1(&directories), 95437);
     }
 
     #[test]
     fn test_part_2() {
         let input = fs::read_to_string("test-input.txt").expect("unable to read test input");
-        let directories = build_directories(&input);
+        let directories = build_directories(&input).unwrap();
         assert_eq!(part_2(&directories), 24933642);
     }
+
+    #[test]
+    fn invalid_terminal_output() {
+        let error = build_directories("$ cd /\n$ ls\n12ab c.txt").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.snippet, "12ab");
+
+        let error = build_directories("$ cd /\n$ rm -rf b").unwrap_err();
+        assert_eq!((error.line, error.snippet.as_str()), (2, "$ rm -rf b"));
+
+        let input = "$ cd /\n$ ls\n1 a\n18446744073709551615 b";
+        let error = build_directories(input).unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+    }
 }
@@ -1,23 +1,21 @@
 // Advent of Code 2022: Day 7
 // https://adventofcode.com/2022/day/7
-// Usage: `cargo run <input-file>`
+// Usage: `cargo run <input-file|directory|->... [--format text|json]`
 
-use day_07::{build_directories, part_1, part_2};
-use std::env;
-use std::fs;
+use aoc_common::{solve_inputs, Args};
+use day_07::Day07;
 
 fn main() {
-    let input_filename = env::args().nth(1).expect("please supply an input filename");
-    let input = fs::read_to_string(&input_filename).expect("failed to read input file");
-    let directories = build_directories(&input);
+    let args = Args::from_env();
+    solve_inputs::<Day07>(&args, |report| {
+        println!(
+            "The sum of the sizes of the directories with at most 100,000 bytes is: {}",
+            report.part_1
+        );
 
-    println!(
-        "The sum of the sizes of the directories with at most 100,000 bytes is: {}",
-        part_1(&directories)
-    );
-
-    println!(
-        "The size of the directory that should be deleted is: {}",
-        part_2(&directories)
-    );
+        println!(
+            "The size of the directory that should be deleted is: {}",
+            report.part_2
+        );
+    });
 }
@@ -0,0 +1,31 @@
+// Generates inputs in the puzzle's format: a square grid of tree heights from
+// 0 to 9. `size` is the width and height of the grid.
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut output = String::with_capacity(size * (size + 1));
+    for _ in 0..size {
+        output.extend((0..size).map(|_| (b'0' + rng.below(10) as u8) as char));
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day08;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 30);
+            let forest = Day08::parse(&input).unwrap();
+            assert_eq!(forest.height(), 30);
+            Day08::part_1(&forest);
+            Day08::part_2(&forest);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+pub mod generator;
+
+use aoc_common::{Direction, Grid, Point, Result, Solution};
+use std::collections::HashSet;
+
+pub fn parse_input(input: &str) -> Result<Grid<u8>> {
+    Grid::parse(Day08::DAY, input, "a tree height from 0 to 9", |c| {
+        c.to_digit(10).map(|height| height as u8)
+    })
+}
+
+pub fn visible_tree_locations(forest: &Grid<u8>) -> HashSet<Point> {
+    let mut visible = HashSet::new();
+
+    // look into the forest from just outside each edge, row by row and
+    // column by column, noting each tree taller than any in front of it
+    for direction in Direction::ALL {
+        let edge = forest
+            .points()
+            .filter(|point| !forest.contains(*point + direction.opposite()));
+
+        for start in edge {
+            let mut tallest_so_far = None;
+            for (point, &height) in forest.ray(start + direction.opposite(), direction) {
+                if Some(height) > tallest_so_far {
+                    tallest_so_far = Some(height);
+                    visible.insert(point);
+                }
+            }
+        }
+    }
+
+    visible
+}
+
+pub fn scenic_score(forest: &Grid<u8>, tree: Point) -> usize {
+    let viewing_height = forest[tree];
+
+    Direction::ALL
+        .iter()
+        .map(|&direction| {
+            let mut visible = 0;
+            for (_, &height) in forest.ray(tree, direction) {
+                visible += 1;
+                if height >= viewing_height {
+                    break;
+                }
+            }
+            visible
+        })
+        .product()
+}
+
+pub fn best_scenic_score(forest: &Grid<u8>) -> usize {
+    forest
+        .points()
+        .map(|tree| scenic_score(forest, tree))
+        .max()
+        .unwrap_or(0)
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: usize = 8;
+
+    type Input<'a> = Grid<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part_1(forest: &Grid<u8>) -> usize {
+        visible_tree_locations(forest).len()
+    }
+
+    fn part_2(forest: &Grid<u8>) -> usize {
+        best_scenic_score(forest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_num_visible_trees() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read test input");
+        let forest = parse_input(&input).unwrap();
+        let locations = visible_tree_locations(&forest);
+
+        assert_eq!(locations.len(), 21);
+    }
+
+    #[test]
+    fn test_scenic_score() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read test input");
+        let forest = parse_input(&input).unwrap();
+
+        assert_eq!(scenic_score(&forest, Point::new(2, 1)), 4);
+        assert_eq!(scenic_score(&forest, Point::new(2, 3)), 8);
+    }
+
+    #[test]
+    fn test_best_scenic_score() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read test input");
+        let forest = parse_input(&input).unwrap();
+
+        assert_eq!(best_scenic_score(&forest), 8);
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let error = parse_input("30373\n25512\n653a2").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 4));
+
+        let error = parse_input("30373\n2551\n65332").unwrap_err();
+        assert_eq!((error.line, error.snippet.as_str()), (2, "2551"));
+    }
+}
@@ -0,0 +1,33 @@
+// Generates inputs in the puzzle's format: one move of the rope's head per
+// line, like "R 4". `size` is the number of moves.
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.choose(&["U", "D", "L", "R"]),
+                rng.range(1..=20)
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day09;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 100);
+            let moves = Day09::parse(&input).unwrap();
+            assert_eq!(moves.len(), 100);
+            Day09::part_1(&moves);
+            Day09::part_2(&moves);
+        }
+    }
+}
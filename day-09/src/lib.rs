@@ -0,0 +1,141 @@
+pub mod generator;
+
+use aoc_common::{parse_number, Direction, ParseError, Point, Result, Solution};
+use std::collections::HashSet;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Move {
+    pub direction: Direction,
+    pub count: usize,
+}
+
+impl Move {
+    pub fn new(s: &str) -> Result<Self> {
+        let (direction, count) = s.split_once(' ').ok_or_else(|| {
+            let message = "expected a direction and a count like \"R 4\"";
+            ParseError::new(Day09::DAY, s, s, message)
+        })?;
+
+        let direction = match direction {
+            "R" => Direction::Right,
+            "L" => Direction::Left,
+            "U" => Direction::Up,
+            "D" => Direction::Down,
+            _ => {
+                let message = format!("expected a direction of R, L, U or D, got {direction:?}");
+                return Err(ParseError::new(Day09::DAY, s, direction, message));
+            }
+        };
+        let count = parse_number(Day09::DAY, s, count)?;
+        Ok(Self { direction, count })
+    }
+}
+
+pub fn parse_head_moves(input: &str) -> Result<Vec<Move>> {
+    input
+        .trim()
+        .split('\n')
+        .map(|line| Move::new(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+pub fn unique_tail_locations(moves: &[Move], num_knots: usize) -> usize {
+    let mut knots = vec![Point::ORIGIN; num_knots];
+    let mut locations = HashSet::new();
+
+    for m in moves {
+        for _ in 0..m.count {
+            step(&mut knots, m.direction);
+            locations.insert(knots[num_knots - 1]);
+        }
+    }
+
+    locations.len()
+}
+
+// moves the head one step and each knot after it to follow along
+pub fn step(knots: &mut [Point], direction: Direction) {
+    knots[0] += direction.offset();
+    for i in 1..knots.len() {
+        knots[i] += tail_move(knots[i - 1], knots[i]);
+    }
+}
+
+pub fn tail_move(head: Point, tail: Point) -> Point {
+    match head - tail {
+        offset if head.chebyshev_distance(&tail) > 1 => offset.signum(),
+        _ => Point::ORIGIN,
+    }
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: usize = 9;
+
+    type Input<'a> = Vec<Move>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse_head_moves(input)
+    }
+
+    fn part_1(head_moves: &Vec<Move>) -> usize {
+        unique_tail_locations(head_moves, 2)
+    }
+
+    fn part_2(head_moves: &Vec<Move>) -> usize {
+        unique_tail_locations(head_moves, 10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use std::fs;
+
+    #[test]
+    fn test_unique_tail_locations() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read test input");
+        let moves = parse_head_moves(&input).unwrap();
+        assert_eq!(unique_tail_locations(&moves, 2), 13);
+    }
+
+    #[test]
+    fn test_unique_long_tail_locations() {
+        let input = fs::read_to_string("test-input-part-2.txt").expect("failed to read test input");
+        let moves = parse_head_moves(&input).unwrap();
+        assert_eq!(unique_tail_locations(&moves, 10), 36);
+    }
+
+    #[test]
+    fn test_parse_head_moves() {
+        let error = parse_head_moves("R 4\nX 4").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = parse_head_moves("R 4\nU -4").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.snippet.as_str()),
+            (2, 3, "-4")
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn knots_stay_touching(
+            directions in vec(proptest::sample::select(Direction::ALL.to_vec()), 0..200),
+            num_knots in 2..=10usize,
+        ) {
+            let mut knots = vec![Point::ORIGIN; num_knots];
+            for direction in directions {
+                step(&mut knots, direction);
+                for pair in knots.windows(2) {
+                    prop_assert!(pair[0].chebyshev_distance(&pair[1]) <= 1);
+                }
+            }
+        }
+    }
+}
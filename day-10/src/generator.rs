@@ -0,0 +1,42 @@
+// Generates inputs in the puzzle's format: one "noop" or "addx V" instruction
+// per line, keeping X on the screen. `size` is the number of cycles the
+// program takes, up to the 240 the CRT can draw.
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let cycles = size.clamp(1, 240);
+    let mut output = String::new();
+    let mut x: isize = 1;
+    let mut cycle = 0;
+
+    while cycle < cycles {
+        if cycle + 2 <= cycles && rng.chance(2, 3) {
+            let v = rng.signed_range(-x.min(15)..=(39 - x).min(15));
+            x += v;
+            cycle += 2;
+            output += &format!("addx {v}\n");
+        } else {
+            cycle += 1;
+            output += "noop\n";
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day10;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 240);
+            let instructions = Day10::parse(&input).unwrap();
+            Day10::part_1(&instructions);
+            assert_eq!(Day10::part_2(&instructions).lines().count(), 6);
+        }
+    }
+}
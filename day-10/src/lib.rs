@@ -0,0 +1,127 @@
+pub mod generator;
+
+use aoc_common::{parse_number, ParseError, Result, Solution};
+
+const CRT_LINE_LENGTH: usize = 40;
+const CRT_LINES: usize = 6;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Instruction {
+    Addx(isize),
+    Noop,
+}
+
+impl Instruction {
+    pub fn new(s: &str) -> Result<Self> {
+        match s.split_once(' ') {
+            None if s == "noop" => Ok(Self::Noop),
+            Some(("addx", n)) => Ok(Self::Addx(parse_number(Day10::DAY, s, n)?)),
+            _ => {
+                let message = "expected \"noop\" or \"addx\" followed by a number";
+                Err(ParseError::new(Day10::DAY, s, s, message))
+            }
+        }
+    }
+}
+
+pub fn parse_instructions(s: &str) -> Result<Vec<Instruction>> {
+    s.trim()
+        .split('\n')
+        .map(|line| Instruction::new(line).map_err(|e| e.within(s, line)))
+        .collect()
+}
+
+fn execute<F>(instructions: &[Instruction], mut side_effect: F)
+where
+    F: FnMut(isize, isize),
+{
+    let mut x = 1;
+    let mut cycles = 1;
+
+    for instruction in instructions {
+        side_effect(cycles, x);
+        cycles += 1;
+
+        match instruction {
+            Instruction::Noop => {}
+            Instruction::Addx(n) => {
+                side_effect(cycles, x);
+                x += n;
+                cycles += 1;
+            }
+        }
+    }
+}
+
+pub fn sum_of_signal_strengths(instructions: &[Instruction]) -> isize {
+    let mut signal_strengths = 0;
+
+    execute(instructions, |cycles, x| {
+        if cycles % 40 == 20 {
+            signal_strengths += cycles * x;
+        }
+    });
+
+    signal_strengths
+}
+
+// renders the CRT as newline separated rows of '#' (lit) and ' ' (dark) pixels
+pub fn render_crt(instructions: &[Instruction]) -> String {
+    let mut crt = [b' '; CRT_LINE_LENGTH * CRT_LINES];
+
+    execute(instructions, |cycles, x| {
+        let draw_index = (cycles % (CRT_LINE_LENGTH as isize)) - 1;
+        if x - 1 <= draw_index && draw_index <= x + 1 {
+            crt[cycles as usize - 1] = b'#';
+        }
+    });
+
+    crt.chunks_exact(CRT_LINE_LENGTH)
+        .map(|line| std::str::from_utf8(line).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: usize = 10;
+
+    type Input<'a> = Vec<Instruction>;
+    type Part1 = isize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse_instructions(input)
+    }
+
+    fn part_1(instructions: &Vec<Instruction>) -> isize {
+        sum_of_signal_strengths(instructions)
+    }
+
+    fn part_2(instructions: &Vec<Instruction>) -> String {
+        render_crt(instructions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_sum_of_signal_strengths() {
+        let input = fs::read_to_string("test-input.txt").unwrap();
+        let instructions = parse_instructions(&input).unwrap();
+        assert_eq!(sum_of_signal_strengths(&instructions), 13140);
+    }
+
+    #[test]
+    fn test_parse_instructions() {
+        let error = parse_instructions("noop\naddx 3\naddx\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+
+        let error = parse_instructions("noop\naddx 3.5").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+    }
+}
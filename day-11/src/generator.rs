@@ -0,0 +1,121 @@
+// Generates inputs in the puzzle's format: notes on each monkey's items,
+// operation, divisibility test and throws. Each test divides by a different
+// prime and only one monkey squares the worry level, like the puzzle's input,
+// so that the worry levels fit in a usize. `size` is the number of monkeys,
+// from 2 to 9. With only a few monkeys the items keep coming back to be
+// squared or multiplied again, so if part 1 keeps overflowing the monkeys
+// fall back to only adding to the worry levels.
+use aoc_common::Rng;
+
+const PRIMES: [usize; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+struct Monkey {
+    items: Vec<usize>,
+    operation: (char, Option<usize>),
+    divisor: usize,
+    targets: [usize; 2],
+}
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // the worry levels aren't kept down in part 1, so try again in the
+    // case that they'd overflow
+    for attempt in 0.. {
+        let monkeys = random_monkeys(rng, size.clamp(2, PRIMES.len()), attempt >= 100);
+        if part_1_fits(&monkeys) {
+            return write(&monkeys);
+        }
+    }
+    unreachable!()
+}
+
+fn random_monkeys(rng: &mut Rng, count: usize, only_adding: bool) -> Vec<Monkey> {
+    let mut divisors = PRIMES.to_vec();
+    rng.shuffle(&mut divisors);
+    let squarer = rng.below(count);
+
+    (0..count)
+        .map(|i| {
+            let operation = match rng.below(2) {
+                _ if only_adding => ('+', Some(rng.range(1..=8))),
+                _ if i == squarer => ('*', None),
+                0 => ('+', Some(rng.range(1..=8))),
+                _ => ('*', Some(rng.range(2..=19))),
+            };
+            let items = (0..rng.range(1..=8)).map(|_| rng.range(50..=99)).collect();
+            let mut other_monkey = || (i + rng.range(1..=count - 1)) % count;
+            Monkey {
+                items,
+                operation,
+                divisor: divisors[i],
+                targets: [other_monkey(), other_monkey()],
+            }
+        })
+        .collect()
+}
+
+// plays the 20 rounds of part 1 with checked arithmetic
+fn part_1_fits(monkeys: &[Monkey]) -> bool {
+    let mut items: Vec<Vec<usize>> = monkeys.iter().map(|m| m.items.clone()).collect();
+    for _ in 0..20 {
+        for (i, monkey) in monkeys.iter().enumerate() {
+            for item in std::mem::take(&mut items[i]) {
+                let operand = monkey.operation.1.unwrap_or(item);
+                let worry = match monkey.operation.0 {
+                    '+' => item.checked_add(operand),
+                    _ => item.checked_mul(operand),
+                };
+                let Some(worry) = worry.map(|worry| worry / 3) else {
+                    return false;
+                };
+                let target = monkey.targets[(worry % monkey.divisor != 0) as usize];
+                items[target].push(worry);
+            }
+        }
+    }
+    true
+}
+
+fn write(monkeys: &[Monkey]) -> String {
+    let notes: Vec<String> = monkeys
+        .iter()
+        .enumerate()
+        .map(|(i, monkey)| {
+            let items: Vec<String> = monkey.items.iter().map(|item| item.to_string()).collect();
+            let operand = match monkey.operation.1 {
+                Some(n) => n.to_string(),
+                None => "old".to_string(),
+            };
+            format!(
+                "Monkey {i}:\n  Starting items: {}\n  Operation: new = old {} {operand}\n  \
+                 Test: divisible by {}\n    If true: throw to monkey {}\n    \
+                 If false: throw to monkey {}\n",
+                items.join(", "),
+                monkey.operation.0,
+                monkey.divisor,
+                monkey.targets[0],
+                monkey.targets[1]
+            )
+        })
+        .collect();
+
+    notes.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day11;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..10 {
+            let size = 2 + seed as usize % 8;
+            let input = generate(&mut Rng::new(seed), size);
+            let monkeys = Day11::parse(&input).unwrap();
+            assert_eq!(monkeys.len(), size);
+            Day11::part_1(&monkeys);
+            Day11::part_2(&monkeys);
+        }
+    }
+}
@@ -1,3 +1,6 @@
+pub mod generator;
+
+use aoc_common::{parse_number, ParseError, Result, Solution};
 use std::collections::VecDeque;
 
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
@@ -30,54 +33,57 @@ pub struct Monkey {
 }
 
 impl Monkey {
-    pub fn new(s: &str) -> Self {
+    pub fn new(s: &str) -> Result<Self> {
         let mut lines = s.split('\n');
 
-        let number = (lines.next().unwrap().as_bytes()[7] - b'0') as usize;
+        // the rest of the next line after the expected text, ignoring indentation
+        let mut next_line = |expected: &str| {
+            let line = lines.next().unwrap_or(&s[s.len()..]);
+            line.trim_start().strip_prefix(expected).ok_or_else(|| {
+                let message = format!("expected a line starting with {expected:?}");
+                ParseError::new(Day11::DAY, s, line, message)
+            })
+        };
+        let int = |at| parse_number(Day11::DAY, s, at);
 
-        let items = lines
-            .next()
-            .unwrap()
-            .split(": ")
-            .nth(1)
-            .unwrap()
-            .split(", ")
-            .map(|s| s.parse().unwrap())
-            .collect();
+        let number = next_line("Monkey ")?;
+        let number = int(number.strip_suffix(':').unwrap_or(number))?;
 
-        let mut operation_iter = lines
-            .next()
-            .unwrap()
-            .split("= old ")
-            .nth(1)
-            .unwrap()
-            .split(' ');
+        let items = next_line("Starting items:")?.trim();
+        let items = match items {
+            "" => VecDeque::new(),
+            items => items.split(", ").map(int).collect::<Result<_>>()?,
+        };
 
-        let operator = match operation_iter.next().unwrap() {
+        let operation = next_line("Operation: new = old ")?;
+        let (operator, operand) = operation
+            .split_once(' ')
+            .unwrap_or((operation, &operation[operation.len()..]));
+        let operator = match operator {
             "+" => Operator::Plus,
             "*" => Operator::Times,
-            _ => panic!("unsupported operator"),
+            _ => {
+                let message = format!("unsupported operator {operator:?}");
+                return Err(ParseError::new(Day11::DAY, s, operator, message));
+            }
         };
-        let operand = match operation_iter.next().unwrap() {
+        let operand = match operand {
             "old" => Operand::Old,
-            n => Operand::Literal(n.parse().unwrap()),
+            n => Operand::Literal(int(n)?),
         };
         let operation = Operation { operator, operand };
 
-        let test_divisor = lines
-            .next()
-            .unwrap()
-            .split("by ")
-            .nth(1)
-            .unwrap()
-            .parse()
-            .unwrap();
-
-        let true_monkey = (lines.next().unwrap().as_bytes().last().unwrap() - b'0') as usize;
+        let test_divisor_str = next_line("Test: divisible by ")?;
+        let test_divisor = int(test_divisor_str)?;
+        if test_divisor == 0 {
+            let message = "can't test for divisibility by zero";
+            return Err(ParseError::new(Day11::DAY, s, test_divisor_str, message));
+        }
 
-        let false_monkey = (lines.next().unwrap().as_bytes().last().unwrap() - b'0') as usize;
+        let true_monkey = int(next_line("If true: throw to monkey ")?)?;
+        let false_monkey = int(next_line("If false: throw to monkey ")?)?;
 
-        Self {
+        Ok(Self {
             number,
             items,
             operation,
@@ -85,7 +91,7 @@ impl Monkey {
             true_monkey,
             false_monkey,
             inspections: 0,
-        }
+        })
     }
 
     // returns (item, monkey) where the item is the worry level
@@ -123,6 +129,71 @@ impl Monkey {
     }
 }
 
+pub fn play_keep_away<F>(monkeys: &mut [Monkey], rounds: usize, manage_worry: &F) -> usize
+where
+    F: Fn(usize) -> usize,
+{
+    for _round in 0..rounds {
+        for i in 0..monkeys.len() {
+            while let Some((item, target_monkey)) = monkeys[i].inspect_and_throw(manage_worry) {
+                monkeys[target_monkey].catch(item);
+            }
+        }
+    }
+
+    monkeys.sort_by_key(|m| m.inspections);
+    monkeys
+        .iter()
+        .rev()
+        .take(2)
+        .map(|m| m.inspections)
+        .product()
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: usize = 11;
+
+    type Input<'a> = Vec<Monkey>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let blocks: Vec<_> = input.trim().split("\n\n").collect();
+        let mut monkeys = vec![];
+
+        for (i, block) in blocks.iter().enumerate() {
+            let monkey = Monkey::new(block).map_err(|e| e.within(input, block))?;
+            let header = block.split('\n').next().unwrap_or(block);
+
+            if monkey.number != i {
+                let message = format!("expected monkey {i} to come next");
+                return Err(ParseError::new(Day11::DAY, input, header, message));
+            }
+            if monkey.true_monkey.max(monkey.false_monkey) >= blocks.len() {
+                let message = format!("monkey {i} throws to a monkey that doesn't exist");
+                return Err(ParseError::new(Day11::DAY, input, header, message));
+            }
+
+            monkeys.push(monkey);
+        }
+
+        Ok(monkeys)
+    }
+
+    fn part_1(monkeys: &Vec<Monkey>) -> usize {
+        play_keep_away(&mut monkeys.clone(), 20, &|worry| worry / 3)
+    }
+
+    fn part_2(monkeys: &Vec<Monkey>) -> usize {
+        let common_divisor: usize = monkeys.iter().map(|m| m.test_divisor).product();
+        play_keep_away(&mut monkeys.clone(), 10_000, &|worry| {
+            worry % common_divisor
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,7 +203,11 @@ mod tests {
     #[test]
     fn monkey_parsing() {
         let input = fs::read_to_string("test-input.txt").unwrap();
-        let monkeys: Vec<_> = input.trim().split("\n\n").map(Monkey::new).collect();
+        let monkeys: Vec<_> = input
+            .trim()
+            .split("\n\n")
+            .map(|s| Monkey::new(s).unwrap())
+            .collect();
 
         let expected_monkeys = vec![
             Monkey {
@@ -212,4 +287,54 @@ mod tests {
         assert_eq!(monkey.inspect_and_throw(divide_by_three), None);
         assert_eq!(monkey.inspections, 2);
     }
+
+    #[test]
+    fn test_part_1() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input file");
+        let mut monkeys: Vec<_> = input
+            .trim()
+            .split("\n\n")
+            .map(|s| Monkey::new(s).unwrap())
+            .collect();
+        assert_eq!(play_keep_away(&mut monkeys, 20, &|worry| worry / 3), 10_605);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input file");
+        let mut monkeys: Vec<_> = input
+            .trim()
+            .split("\n\n")
+            .map(|s| Monkey::new(s).unwrap())
+            .collect();
+        let common_divisor: usize = monkeys.iter().map(|m| m.test_divisor).product();
+
+        assert_eq!(
+            play_keep_away(&mut monkeys, 10_000, &|worry| worry % common_divisor),
+            2_713_310_158
+        );
+    }
+
+    #[test]
+    fn invalid_monkeys() {
+        let input = fs::read_to_string("test-input.txt").unwrap();
+        let input = input.replace("Operation: new = old * 19", "Operation: new = old / 19");
+        let error = Day11::parse(&input).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.snippet.as_str()),
+            (3, 24, "/")
+        );
+
+        let input = "Monkey 0:\n  Starting items: 79, 98\n  Operation: new = old * 19";
+        let error = Day11::parse(input).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 28));
+        assert_eq!(
+            error.message,
+            "expected a line starting with \"Test: divisible by \""
+        );
+
+        let input = "Monkey 0:\n  Starting items: 79, 98\n  Operation: new = old +";
+        let error = Day11::parse(input).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 25));
+    }
 }
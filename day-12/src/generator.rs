@@ -0,0 +1,48 @@
+// Generates inputs in the puzzle's format: a heightmap of letters from a to z
+// with a start S and an end E. Heights rise steadily from left to right, with
+// random dips everywhere except along the start's row, so there's always a
+// way to climb to the end. `size` is the width of the map, at least 26.
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(26);
+    let height = (width / 3).max(5);
+    let start_row = rng.below(height);
+    let end_row = rng.below(height);
+
+    let mut output = String::with_capacity((width + 1) * height);
+    for row in 0..height {
+        for column in 0..width {
+            let elevation = (column * 26 / width) as u8;
+            let c = match (row, column) {
+                (row, 0) if row == start_row => 'S',
+                (row, column) if row == end_row && column == width - 1 => 'E',
+                (row, _) if row != start_row && rng.chance(1, 4) => {
+                    (b'a' + rng.range(0..=elevation as usize) as u8) as char
+                }
+                _ => (b'a' + elevation) as char,
+            };
+            output.push(c);
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day12;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_have_routes() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 40);
+            let grid = Day12::parse(&input).unwrap();
+            assert!(Day12::part_1(&grid).0.is_some());
+            assert!(Day12::part_2(&grid).0.is_some());
+        }
+    }
+}
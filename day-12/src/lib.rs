@@ -0,0 +1,173 @@
+pub mod generator;
+
+use aoc_common::{Grid, Maybe, ParseError, Point, Result, Solution};
+use std::collections::{HashMap, VecDeque};
+
+// a rectangular heightmap of a-z with exactly one start (S) and one end (E)
+pub fn parse_grid(input: &str) -> Result<Grid<u8>> {
+    let grid = Grid::parse(Day12::DAY, input, "a height from a to z, S or E", |c| {
+        matches!(c, 'a'..='z' | 'S' | 'E').then_some(c as u8)
+    })?;
+
+    for marker in ['S', 'E'] {
+        let mut found = input
+            .match_indices(marker)
+            .map(|(i, m)| &input[i..i + m.len()]);
+        let first = found.next();
+        if let Some(second) = found.next() {
+            let message = format!("found more than one {marker}");
+            return Err(ParseError::new(Day12::DAY, input, second, message));
+        }
+        if first.is_none() {
+            let message = format!("the heightmap has no {marker}");
+            return Err(ParseError::new(Day12::DAY, input, input.trim(), message));
+        }
+    }
+
+    Ok(grid)
+}
+
+pub fn locations(grid: &Grid<u8>, c: char) -> Vec<Point> {
+    grid.positions(|&square| square == c as u8).collect()
+}
+
+pub fn fewest_steps(grid: &Grid<u8>, start: Point, end: Point) -> Option<usize> {
+    let mut queue = VecDeque::new();
+    let mut steps = HashMap::new();
+
+    steps.insert(start, 0);
+    queue.push_back(start);
+
+    while let Some(square) = queue.pop_front() {
+        let current_steps: usize = steps[&square] + 1;
+        for adjacent in possible_moves(grid, square) {
+            match steps.get_mut(&adjacent) {
+                Some(prev_steps) => {
+                    if current_steps < *prev_steps {
+                        *prev_steps = current_steps;
+                    }
+                }
+                None => {
+                    steps.insert(adjacent, current_steps);
+                    if adjacent != end {
+                        queue.push_back(adjacent);
+                    }
+                }
+            }
+        }
+    }
+
+    steps.remove(&end)
+}
+
+// the squares next to this one that are at most one higher
+pub fn possible_moves(grid: &Grid<u8>, square: Point) -> impl Iterator<Item = Point> + '_ {
+    let here = grid[square];
+    grid.neighbours(square)
+        .filter(move |&adjacent| legal_move(here, grid[adjacent]))
+}
+
+pub fn legal_move(from: u8, to: u8) -> bool {
+    translate(to) - translate(from) < 2
+}
+
+pub fn translate(height: u8) -> i8 {
+    (match height {
+        b'S' => b'a',
+        b'E' => b'z',
+        n => n,
+    }) as i8
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: usize = 12;
+
+    type Input<'a> = Grid<u8>;
+    type Part1 = Maybe<usize>;
+    type Part2 = Maybe<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse_grid(input)
+    }
+
+    // the shortest route from the start to the end
+    fn part_1(grid: &Grid<u8>) -> Maybe<usize> {
+        let start = locations(grid, 'S')[0];
+        let end = locations(grid, 'E')[0];
+        fewest_steps(grid, start, end).into()
+    }
+
+    // the shortest route from any a (including the start) to the end
+    fn part_2(grid: &Grid<u8>) -> Maybe<usize> {
+        let end = locations(grid, 'E')[0];
+        let mut aes = locations(grid, 'a');
+        aes.push(locations(grid, 'S')[0]);
+
+        aes.into_iter()
+            .flat_map(|a| fewest_steps(grid, a, end))
+            .min()
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_part_1() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input file");
+        let grid = parse_grid(&input).unwrap();
+        let start = locations(&grid, 'S')[0];
+        let end = locations(&grid, 'E')[0];
+        assert_eq!(fewest_steps(&grid, start, end), Some(31));
+    }
+
+    #[test]
+    fn test_part_2() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input file");
+        let grid = parse_grid(&input).unwrap();
+        let aes = locations(&grid, 'a');
+        let end = locations(&grid, 'E')[0];
+
+        let shortest_a_to_end: usize = aes
+            .into_iter()
+            .flat_map(|a| fewest_steps(&grid, a, end))
+            .min()
+            .unwrap();
+
+        assert_eq!(shortest_a_to_end, 29);
+    }
+
+    #[test]
+    fn test_possible_moves() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input file");
+        let grid = parse_grid(&input).unwrap();
+
+        let moves: Vec<_> = possible_moves(&grid, Point::new(0, 0)).collect();
+        assert_eq!(moves, [Point::new(1, 0), Point::new(0, 1)]);
+
+        // c can't climb to the s to its right
+        let moves: Vec<_> = possible_moves(&grid, Point::new(2, 2)).collect();
+        assert_eq!(
+            moves,
+            [Point::new(2, 1), Point::new(2, 3), Point::new(1, 2)]
+        );
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        let error = parse_grid("SabE\nabcS").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.message, "found more than one S");
+
+        let error = parse_grid("Sab\nabE\na-c").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 2));
+
+        let error = parse_grid("Sab\nabc").unwrap_err();
+        assert_eq!(error.message, "the heightmap has no E");
+    }
+}
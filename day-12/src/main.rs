@@ -1,160 +1,20 @@
 // Advent of Code 2022: Day 12
 // https://adventofcode.com/2022/day/12
-// Usage: `cargo run <input-file>`
+// Usage: `cargo run <input-file|directory|->... [--format text|json]`
 
-use std::collections::{HashMap, VecDeque};
-use std::env;
-use std::fs;
+use aoc_common::{solve_inputs, Args};
+use day_12::Day12;
 
 fn main() {
-    let input_filename = env::args().nth(1).expect("please supply an input filename");
-    let input = fs::read_to_string(&input_filename).expect("failed to read input file");
-    let grid: Vec<&[u8]> = input.trim().split('\n').map(|s| s.as_bytes()).collect();
-
-    let start = locations(&grid, 'S')[0];
-    let end = locations(&grid, 'E')[0];
-    let mut aes = locations(&grid, 'a');
-    aes.push(start);
-
-    if let Some(n) = fewest_steps(&grid, start, end) {
-        println!("The shortest route from start to end is {n} steps",);
-    } else {
-        println!("Couldn't find a route from the start to the end!");
-    }
-
-    let shortest_a_to_end = aes
-        .into_iter()
-        .flat_map(|a| fewest_steps(&grid, a, end))
-        .min()
-        .unwrap();
-
-    println!(
-        "The shortest route from any a to the end is {} steps",
-        shortest_a_to_end
-    );
-}
-
-fn locations(grid: &[&[u8]], c: char) -> Vec<(usize, usize)> {
-    let mut results = vec![];
-    for (rowi, row) in grid.iter().enumerate() {
-        for (coli, val) in row.iter().enumerate() {
-            if *val == c as u8 {
-                results.push((rowi, coli));
-            }
-        }
-    }
-    results
-}
-
-fn fewest_steps(grid: &[&[u8]], start: (usize, usize), end: (usize, usize)) -> Option<usize> {
-    let mut queue = VecDeque::new();
-    let mut steps = HashMap::new();
-    let mut possible_moves: [Option<(usize, usize)>; 4] = [None; 4];
-
-    steps.insert(start, 0);
-    queue.push_back(start);
-
-    while let Some((row, col)) = queue.pop_front() {
-        update_possible_moves(grid, &mut possible_moves, row, col);
-
-        let current_steps: usize = steps[&(row, col)] + 1;
-        for (adj_row, adj_col) in possible_moves.into_iter().flatten() {
-            match steps.get_mut(&(adj_row, adj_col)) {
-                Some(prev_steps) => {
-                    if current_steps < *prev_steps {
-                        *prev_steps = current_steps;
-                    }
-                }
-                None => {
-                    steps.insert((adj_row, adj_col), current_steps);
-                    if (adj_row, adj_col) != end {
-                        queue.push_back((adj_row, adj_col));
-                    }
-                }
-            }
-        }
-    }
-
-    steps.remove(&end)
-}
-
-fn update_possible_moves(
-    grid: &[&[u8]],
-    possible_moves: &mut [Option<(usize, usize)>; 4],
-    row: usize,
-    col: usize,
-) {
-    let max_row_index = grid.len() - 1;
-    let max_col_index = grid[0].len() - 1;
-    let here = grid[row][col];
-
-    possible_moves[0] = match row > 0 && legal_move(here, grid[row - 1][col]) {
-        true => Some((row - 1, col)),
-        false => None,
-    };
-    possible_moves[1] = match col < max_col_index && legal_move(here, grid[row][col + 1]) {
-        true => Some((row, col + 1)),
-        false => None,
-    };
-    possible_moves[2] = match row < max_row_index && legal_move(here, grid[row + 1][col]) {
-        true => Some((row + 1, col)),
-        false => None,
-    };
-    possible_moves[3] = match col > 0 && legal_move(here, grid[row][col - 1]) {
-        true => Some((row, col - 1)),
-        false => None,
-    };
-}
-
-fn legal_move(from: u8, to: u8) -> bool {
-    translate(to) - translate(from) < 2
-}
-
-fn translate(height: u8) -> i8 {
-    (match height {
-        b'S' => b'a',
-        b'E' => b'z',
-        n => n,
-    }) as i8
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_1() {
-        let input = fs::read_to_string("test-input.txt").expect("failed to read input file");
-        let grid: Vec<&[u8]> = input.trim().split('\n').map(|s| s.as_bytes()).collect();
-        let start = locations(&grid, 'S')[0];
-        let end = locations(&grid, 'E')[0];
-        assert_eq!(fewest_steps(&grid, start, end), Some(31));
-    }
-
-    #[test]
-    fn test_part_2() {
-        let input = fs::read_to_string("test-input.txt").expect("failed to read input file");
-        let grid: Vec<&[u8]> = input.trim().split('\n').map(|s| s.as_bytes()).collect();
-        let aes = locations(&grid, 'a');
-        let end = locations(&grid, 'E')[0];
-
-        let shortest_a_to_end: usize = aes
-            .into_iter()
-            .flat_map(|a| fewest_steps(&grid, a, end))
-            .min()
-            .unwrap();
-
-        assert_eq!(shortest_a_to_end, 29);
-    }
-
-    #[test]
-    fn test_update_possible_moves() {
-        let input = fs::read_to_string("test-input.txt").expect("failed to read input file");
-        let grid: Vec<&[u8]> = input.trim().split('\n').map(|s| s.as_bytes()).collect();
-
-        let mut possible_moves = [None; 4];
-        update_possible_moves(&grid, &mut possible_moves, 0, 0);
-
-        assert_eq!(possible_moves, [None, Some((0, 1)), Some((1, 0)), None]);
-    }
+    let args = Args::from_env();
+    solve_inputs::<Day12>(&args, |report| {
+        println!(
+            "The shortest route from start to end is {} steps",
+            report.part_1
+        );
+        println!(
+            "The shortest route from any a to the end is {} steps",
+            report.part_2
+        );
+    });
 }
@@ -0,0 +1,39 @@
+// Generates inputs in the puzzle's format: pairs of nested packets like
+// "[1,[2,3],[]]", with a blank line between pairs. `size` is the number of
+// pairs.
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let pairs: Vec<String> = (0..size.max(1))
+        .map(|_| format!("{}\n{}\n", packet(rng, 0), packet(rng, 0)))
+        .collect();
+    pairs.join("\n")
+}
+
+fn packet(rng: &mut Rng, depth: usize) -> String {
+    let elements: Vec<String> = (0..rng.range(0..=5))
+        .map(|_| match depth < 4 && rng.chance(1, 3) {
+            true => packet(rng, depth + 1),
+            false => rng.range(0..=10).to_string(),
+        })
+        .collect();
+    format!("[{}]", elements.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day13;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 50);
+            let pairs = Day13::parse(&input).unwrap();
+            assert_eq!(pairs.len(), 50);
+            Day13::part_1(&pairs);
+            Day13::part_2(&pairs);
+        }
+    }
+}
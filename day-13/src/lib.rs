@@ -1,6 +1,14 @@
+pub mod generator;
+
+use aoc_common::{parse_number, ParseError, Result, Solution};
 use std::cmp::Ordering;
+use std::fmt;
 use Element::{Int, List};
 
+// parsing, comparing and dropping packets all recurse into nested lists, so
+// this keeps a pathological packet from overflowing the stack
+const MAX_DEPTH: usize = 256;
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Element {
     List(Vec<Element>),
@@ -8,81 +16,96 @@ pub enum Element {
 }
 
 impl Element {
-    pub fn new(s: &str) -> Self {
-        Self::from_bytes(s.as_bytes())
+    pub fn new(s: &str) -> Result<Self> {
+        let (element, rest) = Self::parse_element(s, s, 0)?;
+        if !rest.is_empty() {
+            return Err(unexpected(s, rest, "expected the packet to end"));
+        }
+        Ok(element)
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Self {
-        match bytes[0] {
-            b'[' => {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        match std::str::from_utf8(bytes) {
+            Ok(s) => Self::new(s),
+            Err(error) => {
+                // point just past the part that we could read
+                let valid = std::str::from_utf8(&bytes[..error.valid_up_to()]).unwrap();
+                let message = "expected a packet made of text";
+                Err(ParseError::new(
+                    Day13::DAY,
+                    valid,
+                    &valid[valid.len()..],
+                    message,
+                ))
+            }
+        }
+    }
+
+    // parses the element at the start of s (a slice of source) and nested
+    // depth lists deep, returning it along with whatever follows it
+    fn parse_element<'a>(source: &str, s: &'a str, depth: usize) -> Result<(Self, &'a str)> {
+        match s.as_bytes().first() {
+            Some(b'[') if depth == MAX_DEPTH => {
+                let message = format!("expected lists nested at most {MAX_DEPTH} deep");
+                Err(ParseError::new(Day13::DAY, source, &s[..1], message))
+            }
+            Some(b'[') => {
                 // this element is a list
                 let mut list = vec![];
-                let mut cursor = 0;
+                let mut rest = &s[1..];
+                if let Some(rest) = rest.strip_prefix(']') {
+                    return Ok((List(list), rest));
+                }
+
                 loop {
-                    cursor += 1;
-                    match bytes[cursor] {
-                        b'[' => {
-                            // found an inner list within the outer list
-                            let start = cursor;
-                            let mut nested_depth = 0;
-                            for (i, b) in bytes[(start + 1)..].iter().enumerate() {
-                                match b {
-                                    b']' => {
-                                        if nested_depth == 0 {
-                                            cursor = start + 1 + i;
-                                            break;
-                                        }
-                                        nested_depth -= 1;
-                                    }
-                                    b'[' => {
-                                        nested_depth += 1;
-                                    }
-                                    _ => {}
-                                }
-                            }
-                            list.push(Element::from_bytes(&bytes[start..=cursor]));
-                        }
-                        b']' => {
-                            // end of the outer list, so we're done
-                            break;
-                        }
-                        b',' => {
-                            // comma separating items in the outer list, continue
-                        }
-                        n if (b'0'..=b'9').contains(&n) => {
-                            // found a number element in the outer list
-                            let start = cursor;
-                            for (i, b) in bytes[(start + 1)..].iter().enumerate() {
-                                if !(b'0'..=b'9').contains(b) {
-                                    cursor = start + i;
-                                    break;
-                                }
-                            }
-                            list.push(Element::from_bytes(&bytes[start..=cursor]));
-                        }
-                        n => {
-                            panic!("Unexpected character {}!", n as char);
-                        }
+                    let (element, after) = Self::parse_element(source, rest, depth + 1)?;
+                    list.push(element);
+                    match after.as_bytes().first() {
+                        Some(b',') => rest = &after[1..],
+                        Some(b']') => return Ok((List(list), &after[1..])),
+                        _ => return Err(unexpected(source, after, "expected , or ]")),
                     }
-
-                    if cursor == bytes.len() - 1 {
-                        break;
-                    };
                 }
-                List(list)
             }
-            n if (b'0'..=b'9').contains(&n) => {
+            Some(b'0'..=b'9') => {
                 // this element is a number
-                let num = bytes
-                    .iter()
-                    .rev()
-                    .enumerate()
-                    .fold(0, |result, (j, digit)| {
-                        result + (*digit as usize - b'0' as usize) * (10usize.pow(j as u32))
-                    });
-                Int(num)
+                let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+                let num = parse_number(Day13::DAY, source, &s[..end])?;
+                Ok((Int(num), &s[end..]))
+            }
+            _ => Err(unexpected(source, s, "expected a list or an integer")),
+        }
+    }
+}
+
+// an error pointing at the first character of rest, or the end of the source
+fn unexpected(source: &str, rest: &str, message: &str) -> ParseError {
+    let at = match rest.chars().next() {
+        Some(c) => &rest[..c.len_utf8()],
+        None => rest,
+    };
+    let message = match at {
+        "" => format!("{message}, got the end of the packet"),
+        at => format!("{message}, got {at:?}"),
+    };
+    ParseError::new(Day13::DAY, source, at, message)
+}
+
+// writes the packet back out the way it appears in the input
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Int(n) => write!(f, "{n}"),
+            List(list) => {
+                write!(f, "[")?;
+                for (i, element) in list.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                write!(f, "]")
             }
-            _ => panic!("Not a list or an int!"),
         }
     }
 }
@@ -104,28 +127,107 @@ impl PartialOrd for Element {
     }
 }
 
+pub fn parse_input(input: &str) -> Result<Vec<(Element, Element)>> {
+    input
+        .trim()
+        .split("\n\n")
+        .map(|pair_of_lines| {
+            let mut lines = pair_of_lines.split('\n');
+            let mut next_element = || match lines.next() {
+                Some(line) => Element::new(line).map_err(|e| e.within(input, line)),
+                None => {
+                    let message = "expected a pair of packets on consecutive lines";
+                    Err(ParseError::new(Day13::DAY, input, pair_of_lines, message))
+                }
+            };
+            Ok((next_element()?, next_element()?))
+        })
+        .collect()
+}
+
+pub fn part_1(element_pairs: &[(Element, Element)]) -> usize {
+    element_pairs
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (i, (first, second))| {
+            acc + if first < second { i + 1 } else { 0 }
+        })
+}
+
+pub fn part_2(pairs: &[(Element, Element)]) -> usize {
+    let mut elements: Vec<&Element> = Vec::with_capacity(pairs.len() * 2 + 2);
+    let markers = [
+        List(vec![List(vec![Int(2)])]),
+        List(vec![List(vec![Int(6)])]),
+    ];
+
+    for (first, second) in pairs {
+        elements.push(first);
+        elements.push(second);
+    }
+    elements.push(&markers[0]);
+    elements.push(&markers[1]);
+
+    elements.sort_unstable();
+
+    markers
+        .iter()
+        .map(|marker| elements.iter().position(|e| *e == marker).unwrap() + 1)
+        .product()
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: usize = 13;
+
+    type Input<'a> = Vec<(Element, Element)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part_1(pairs: &Vec<(Element, Element)>) -> usize {
+        part_1(pairs)
+    }
+
+    fn part_2(pairs: &Vec<(Element, Element)>) -> usize {
+        part_2(pairs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+    use std::fs;
     use Element::{Int, List};
 
     #[test]
     fn parse_just_int() {
-        assert_eq!(Element::new("7"), Int(7));
-        assert_eq!(Element::new("23"), Int(23));
-        assert_eq!(Element::new("1010"), Int(1010));
+        assert_eq!(Element::new("7"), Ok(Int(7)));
+        assert_eq!(Element::new("23"), Ok(Int(23)));
+        assert_eq!(Element::new("1010"), Ok(Int(1010)));
     }
 
     #[test]
     fn parse_list_of_int() {
-        assert_eq!(Element::new("[1,2,3]"), List(vec![Int(1), Int(2), Int(3)]))
+        assert_eq!(
+            Element::new("[1,2,3]"),
+            Ok(List(vec![Int(1), Int(2), Int(3)]))
+        )
     }
 
     #[test]
     fn parse_list_of_lists() {
         assert_eq!(
             Element::new("[[1],[2,3,4]]"),
-            List(vec![List(vec![Int(1)]), List(vec![Int(2), Int(3), Int(4)])])
+            Ok(List(vec![
+                List(vec![Int(1)]),
+                List(vec![Int(2), Int(3), Int(4)])
+            ]))
         );
     }
 
@@ -133,7 +235,7 @@ mod tests {
     fn parse_mixed_content() {
         assert_eq!(
             Element::new("[[4,4],4,4]"),
-            List(vec![List(vec![Int(4), Int(4)]), Int(4), Int(4)])
+            Ok(List(vec![List(vec![Int(4), Int(4)]), Int(4), Int(4)]))
         )
     }
 
@@ -141,7 +243,7 @@ mod tests {
     fn parse_deeply_nested_lists() {
         assert_eq!(
             Element::new("[1,[2,[3,[4,[5,6,0]]]],8,9]"),
-            List(vec![
+            Ok(List(vec![
                 Int(1),
                 List(vec![
                     Int(2),
@@ -152,13 +254,76 @@ mod tests {
                 ]),
                 Int(8),
                 Int(9)
-            ])
+            ]))
         )
     }
 
+    #[test]
+    fn invalid_packets() {
+        let error = parse_input("[1,2]\n[3,4\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.message, "expected , or ], got the end of the packet");
+
+        let error = parse_input("[1,2]\n[x]\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+
+        let deep = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+        let error = Element::new(&deep).unwrap_err();
+        assert_eq!(error.column, MAX_DEPTH + 1);
+
+        let error = parse_input("[1,2]\n").unwrap_err();
+        assert_eq!(
+            error.message,
+            "expected a pair of packets on consecutive lines"
+        );
+    }
+
     #[test]
     fn compare_ints() {
         assert!(Int(1) < Int(2));
         assert!(Int(2) == Int(2));
     }
+
+    #[test]
+    fn test_part_1() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input file");
+        let pairs = parse_input(&input).unwrap();
+        assert_eq!(part_1(&pairs), 13);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input file");
+        let pairs = parse_input(&input).unwrap();
+        assert_eq!(part_2(&pairs), 140);
+    }
+
+    fn element() -> impl Strategy<Value = Element> {
+        (0..11usize)
+            .prop_map(Int)
+            .prop_recursive(4, 32, 5, |inner| {
+                proptest::collection::vec(inner, 0..5).prop_map(List)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn packets_round_trip(element in element()) {
+            prop_assert_eq!(Element::new(&element.to_string()), Ok(element));
+        }
+
+        // Int(2) and List([Int(2)]) compare as equal without being the same
+        // packet, so this is a total order on how packets compare
+        #[test]
+        fn ordering_is_total(a in element(), b in element(), c in element()) {
+            prop_assert_eq!(a.cmp(&a), Ordering::Equal);
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            if a <= b && b <= c {
+                prop_assert!(a <= c);
+            }
+            if a.cmp(&b) == Ordering::Equal {
+                prop_assert_eq!(a.cmp(&c), b.cmp(&c));
+            }
+        }
+    }
 }
@@ -0,0 +1,46 @@
+// Generates inputs in the puzzle's format: one path of rock per line, made of
+// horizontal and vertical lines like "498,4 -> 498,6 -> 496,6", below the
+// sand's source at 500,0. `size` is the number of paths.
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // keep the cave shallow enough that sand piling up on the floor never
+    // reaches x = 0
+    let depth = 10 + size.min(150);
+    let spread = depth / 2 + 5;
+
+    (0..size.max(1))
+        .map(|_| {
+            let mut x = rng.range(500 - spread..=500 + spread);
+            let mut y = rng.range(5..=depth);
+            let mut points = vec![format!("{x},{y}")];
+            for i in 0..rng.range(1..=4) {
+                if i % 2 == 0 {
+                    x = rng
+                        .range(x.saturating_sub(8).max(500 - spread)..=(x + 8).min(500 + spread));
+                } else {
+                    y = rng.range(y.saturating_sub(8).max(5)..=(y + 8).min(depth));
+                }
+                points.push(format!("{x},{y}"));
+            }
+            points.join(" -> ") + "\n"
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day14;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 20);
+            let cave = Day14::parse(&input).unwrap();
+            Day14::part_1(&cave);
+            Day14::part_2(&cave);
+        }
+    }
+}
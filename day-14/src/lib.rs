@@ -1,21 +1,33 @@
+pub mod generator;
+
+use aoc_common::{parse_number, Direction, Grid, ParseError, Point, Result, Solution};
 use std::collections::HashMap;
 use std::ops::RangeInclusive;
 
-#[derive(Clone)]
+// Sand pours in at 500,0 and, with a floor, spreads one column further each
+// row down. Keeping the rock above y = 400 keeps that spread within the cave
+// from x = 0 to 1000, and these limits keep the cave's map a manageable size.
+const MAX_X: usize = 1000;
+const MAX_Y: usize = 400;
+pub const SOURCE: Point = Point::new(500, 0);
+
+#[derive(Debug, Clone)]
 pub struct Cave {
-    pub map: HashMap<(usize, usize), char>,
+    // '#' for rock, 'o' for sand and '.' for air, down to just above the
+    // deepest possible floor
+    pub map: Grid<char>,
     pub deepest_rocks: HashMap<usize, usize>,
     pub floor: Option<usize>,
     pub grains_at_rest: usize,
 }
 
 impl Cave {
-    pub fn new(input: &str) -> Self {
-        let mut map = HashMap::new();
+    pub fn new(input: &str) -> Result<Self> {
+        let mut map = Grid::filled(MAX_X + 1, MAX_Y + 2, '.');
         let mut deepest_rocks = HashMap::new();
 
         let mut add_rock = |x, y| {
-            map.insert((x, y), '#');
+            map[Point::new(x as isize, y as isize)] = '#';
             deepest_rocks
                 .entry(x)
                 .and_modify(|deepest_y| {
@@ -28,12 +40,28 @@ impl Cave {
 
         for line in input.trim().split('\n') {
             let mut segments = line.split(" -> ").map(|segment| {
-                let (x_str, y_str) = segment.split_once(',').unwrap();
-                (x_str.parse().unwrap(), y_str.parse().unwrap())
+                let (x_str, y_str) = segment.split_once(',').ok_or_else(|| {
+                    let message = "expected a point written as x,y";
+                    ParseError::new(Day14::DAY, input, segment, message)
+                })?;
+                let x = parse_number(Day14::DAY, input, x_str)?;
+                let y = parse_number(Day14::DAY, input, y_str)?;
+                if x > MAX_X || y > MAX_Y {
+                    let message = format!("expected x up to {MAX_X} and y up to {MAX_Y}");
+                    return Err(ParseError::new(Day14::DAY, input, segment, message));
+                }
+                Ok((segment, x, y))
             });
 
-            let (mut start_x, mut start_y) = segments.next().unwrap();
-            for (end_x, end_y) in segments {
+            // split always yields at least one segment
+            let (_, mut start_x, mut start_y) = segments.next().unwrap()?;
+            for segment in segments {
+                let (segment, end_x, end_y) = segment?;
+                if start_x != end_x && start_y != end_y {
+                    let message = "expected a horizontal or vertical line of rock";
+                    return Err(ParseError::new(Day14::DAY, input, segment, message));
+                }
+
                 if start_x == end_x {
                     for y in smart_range(start_y, end_y) {
                         add_rock(start_x, y);
@@ -47,12 +75,12 @@ impl Cave {
             }
         }
 
-        Self {
+        Ok(Self {
             map,
             deepest_rocks,
             grains_at_rest: 0,
             floor: None,
-        }
+        })
     }
 
     pub fn build_floor(&mut self) {
@@ -61,44 +89,45 @@ impl Cave {
 
     // drop a grain of sand and return the position at which it rests,
     // or None if it falls forever or if the cave is totally full
-    pub fn drop_sand(&mut self, x: usize, y: usize) -> Option<(usize, usize)> {
+    pub fn drop_sand(&mut self, at: Point) -> Option<Point> {
         // cave is full
-        if self.map.get(&(x, y)).is_some() {
+        if self.map[at] != '.' {
             return None;
         }
 
         // falling infinitely
         if self.floor.is_none() {
-            match self.deepest_rocks.get(&x) {
+            match self.deepest_rocks.get(&(at.x as usize)) {
                 None => return None,
                 Some(deepest_y) => {
-                    if y >= *deepest_y {
+                    if at.y as usize >= *deepest_y {
                         return None;
                     }
                 }
             }
         }
 
-        if self.is_open(x, y + 1) {
-            self.drop_sand(x, y + 1)
-        } else if self.is_open(x - 1, y + 1) {
-            self.drop_sand(x - 1, y + 1)
-        } else if self.is_open(x + 1, y + 1) {
-            self.drop_sand(x + 1, y + 1)
-        } else {
-            self.grains_at_rest += 1;
-            self.map.insert((x, y), 'o');
-            Some((x, y))
+        let below = at + Direction::Down;
+        match [below, below + Direction::Left, below + Direction::Right]
+            .into_iter()
+            .find(|next| self.is_open(*next))
+        {
+            Some(next) => self.drop_sand(next),
+            None => {
+                self.grains_at_rest += 1;
+                self.map[at] = 'o';
+                Some(at)
+            }
         }
     }
 
-    pub fn is_open(&self, x: usize, y: usize) -> bool {
+    pub fn is_open(&self, at: Point) -> bool {
         let at_the_floor = match self.floor {
-            Some(floor) => y == floor,
+            Some(floor) => at.y as usize == floor,
             None => false,
         };
 
-        !at_the_floor && self.map.get(&(x, y)).is_none()
+        !at_the_floor && self.map[at] == '.'
     }
 }
 
@@ -110,6 +139,33 @@ fn smart_range(a: usize, b: usize) -> RangeInclusive<usize> {
     }
 }
 
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: usize = 14;
+
+    type Input<'a> = Cave;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Cave::new(input)
+    }
+
+    fn part_1(cave: &Cave) -> usize {
+        let mut cave = cave.clone();
+        while cave.drop_sand(SOURCE).is_some() {}
+        cave.grains_at_rest
+    }
+
+    fn part_2(cave: &Cave) -> usize {
+        let mut cave_with_floor = cave.clone();
+        cave_with_floor.build_floor();
+        while cave_with_floor.drop_sand(SOURCE).is_some() {}
+        cave_with_floor.grains_at_rest
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,31 +173,44 @@ mod tests {
     #[test]
     fn test_drop_sand_no_floor() {
         let input = std::fs::read_to_string("test-input.txt").unwrap();
-        let mut cave = Cave::new(&input);
-        cave.drop_sand(500, 0);
+        let mut cave = Cave::new(&input).unwrap();
+        cave.drop_sand(SOURCE);
 
         assert_eq!(cave.grains_at_rest, 1);
-        assert_eq!(cave.map.get(&(500, 8)), Some(&'o'));
+        assert_eq!(cave.map[Point::new(500, 8)], 'o');
+    }
+
+    #[test]
+    fn invalid_paths() {
+        let error = Cave::new("498,4 -> 498,6\n503,4 -> 502,x").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 14));
+
+        let error = Cave::new("498,4 -> 498,6 -> 496,8").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 19));
+        assert_eq!(error.snippet, "496,8");
+
+        let error = Cave::new("498,4 -> 498,1000000000000").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 10));
     }
 
     #[test]
     fn part_1() {
         let input = std::fs::read_to_string("test-input.txt").unwrap();
-        let mut cave = Cave::new(&input);
+        let mut cave = Cave::new(&input).unwrap();
 
-        while cave.drop_sand(500, 0).is_some() {}
+        while cave.drop_sand(SOURCE).is_some() {}
         assert_eq!(cave.grains_at_rest, 24);
     }
 
     #[test]
     fn part_2() {
         let input = std::fs::read_to_string("test-input.txt").unwrap();
-        let mut cave_with_floor = Cave::new(&input);
+        let mut cave_with_floor = Cave::new(&input).unwrap();
 
         cave_with_floor.build_floor();
         assert_eq!(cave_with_floor.floor, Some(11));
 
-        while cave_with_floor.drop_sand(500, 0) != Some((500, 0)) {}
+        while cave_with_floor.drop_sand(SOURCE) != Some(SOURCE) {}
         assert_eq!(cave_with_floor.grains_at_rest, 93);
     }
 }
@@ -1,28 +1,21 @@
 // Advent of Code 2022: Day 14
 // https://adventofcode.com/2022/day/14
-// Usage: `cargo run <input-file>`
+// Usage: `cargo run <input-file|directory|->... [--format text|json]`
 
-use day_14::Cave;
-use std::env;
-use std::fs;
+use aoc_common::{solve_inputs, Args};
+use day_14::Day14;
 
 fn main() {
-    let input_filename = env::args().nth(1).expect("please supply an input filename");
-    let input = fs::read_to_string(&input_filename).expect("failed to read input file");
-    let mut cave = Cave::new(&input);
+    let args = Args::from_env();
+    solve_inputs::<Day14>(&args, |report| {
+        println!(
+            "The number of grains that came to rest in the cave with no floor were {}",
+            report.part_1
+        );
 
-    let mut cave_with_floor = cave.clone();
-    cave_with_floor.build_floor();
-
-    while cave.drop_sand(500, 0).is_some() {}
-    println!(
-        "The number of grains that came to rest in the cave with no floor were {}",
-        cave.grains_at_rest
-    );
-
-    while cave_with_floor.drop_sand(500, 0).is_some() {}
-    println!(
-        "The number of grains that came to rest in the cave with no floor were {}",
-        cave_with_floor.grains_at_rest
-    );
+        println!(
+            "The number of grains that came to rest in the cave with a floor were {}",
+            report.part_2
+        );
+    });
 }
@@ -0,0 +1,55 @@
+// Generates inputs in the puzzle's format: one sensor and its closest beacon
+// per line, like "Sensor at x=2, y=18: closest beacon is at x=-2, y=15".
+// Every sensor's beacon is closer than a hidden distress beacon, which
+// therefore can't be ruled out. `size` is the number of sensors; inputs with
+// up to 14 sensors (as many as the example) fit in the example's 0 to 20
+// search area and larger ones in the full 0 to 4,000,000.
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let area = if size <= 14 { 20 } else { 4_000_000 };
+    let distress_beacon = (rng.signed_range(0..=area), rng.signed_range(0..=area));
+
+    (0..size.max(1))
+        .map(|_| {
+            // sensors need room for a beacon closer than the distress beacon
+            let (x, y) = loop {
+                let sensor = (rng.signed_range(0..=area), rng.signed_range(0..=area));
+                if manhattan_distance(sensor, distress_beacon) > 1 {
+                    break sensor;
+                }
+            };
+            let radius = rng.signed_range(1..=manhattan_distance((x, y), distress_beacon) - 1);
+            let dx = rng.signed_range(-radius..=radius);
+            let dy = (radius - dx.abs()) * if rng.chance(1, 2) { 1 } else { -1 };
+
+            format!(
+                "Sensor at x={x}, y={y}: closest beacon is at x={}, y={}\n",
+                x + dx,
+                y + dy
+            )
+        })
+        .collect()
+}
+
+fn manhattan_distance((ax, ay): (isize, isize), (bx, by): (isize, isize)) -> isize {
+    (ax - bx).abs() + (ay - by).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{scan_parameters, Day15};
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 10);
+            let sensors = Day15::parse(&input).unwrap();
+            assert_eq!(scan_parameters(&sensors).0, 10);
+            Day15::part_1(&sensors);
+            assert!(Day15::part_2(&sensors).0.is_some());
+        }
+    }
+}
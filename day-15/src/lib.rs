@@ -1,24 +1,10 @@
-#![feature(hash_drain_filter)]
+pub mod generator;
 
+use aoc_common::{parse_number, Maybe, ParseError, Point, Result, Solution};
+use regex::Regex;
 use std::cmp::{max, min};
 use std::collections::HashSet;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-pub struct Point {
-    pub x: isize,
-    pub y: isize,
-}
-
-impl Point {
-    pub fn new(x: isize, y: isize) -> Self {
-        Self { x, y }
-    }
-
-    pub fn manhattan_distance(&self, other: &Self) -> isize {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
-    }
-}
-
 #[derive(Debug, PartialEq, Eq)]
 pub struct Sensor {
     pub location: Point,
@@ -54,6 +40,8 @@ pub struct XRange {
     pub max: isize,
 }
 
+// ranges always hold at least one x, so there is no is_empty
+#[allow(clippy::len_without_is_empty)]
 impl XRange {
     pub fn new(min: isize, max: isize) -> Self {
         assert!(min <= max);
@@ -65,7 +53,7 @@ impl XRange {
     }
 
     pub fn mergeable(&self, other: &Self) -> bool {
-        self.intersects(&other) || self.adjacent_to(&other)
+        self.intersects(other) || self.adjacent_to(other)
     }
 
     pub fn intersects(&self, other: &Self) -> bool {
@@ -78,8 +66,8 @@ impl XRange {
     }
 
     pub fn merge(&mut self, other: &Self) {
-        if !self.mergeable(&other) {
-            assert!(self.mergeable(&other));
+        if !self.mergeable(other) {
+            assert!(self.mergeable(other));
         }
 
         self.min = min(self.min, other.min);
@@ -102,7 +90,7 @@ impl XRange {
     }
 
     pub fn len(&self) -> usize {
-        (self.max - self.min).abs() as usize + 1
+        (self.max - self.min).unsigned_abs() + 1
     }
 }
 
@@ -111,6 +99,12 @@ pub struct Coverage {
     pub ranges: HashSet<XRange>,
 }
 
+impl Default for Coverage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Coverage {
     pub fn new() -> Self {
         Self {
@@ -119,11 +113,11 @@ impl Coverage {
     }
 
     pub fn add_range(&mut self, mut new_range: XRange) {
-        self.ranges.retain(|range| !new_range.contains(&range));
+        self.ranges.retain(|range| !new_range.contains(range));
 
         let mergeable_ranges: Vec<_> = self
             .ranges
-            .drain_filter(|range| range.mergeable(&new_range))
+            .extract_if(|range| range.mergeable(&new_range))
             .collect();
 
         for range in mergeable_ranges {
@@ -136,11 +130,133 @@ impl Coverage {
     pub fn len(&self) -> usize {
         self.ranges.iter().map(|r| r.len()).sum()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+// far bigger than the puzzle's coordinates, but small enough that distances
+// and the ranges worked out from them can't overflow
+const MAX_COORDINATE: isize = 1 << 40;
+
+pub fn parse_input(input: &str) -> Result<Vec<Sensor>> {
+    let d = "(-?[0-9]+)";
+    let format = format!(r"Sensor at x={d}, y={d}: closest beacon is at x={d}, y={d}");
+    let re = Regex::new(&format[..]).unwrap();
+
+    input
+        .trim()
+        .split('\n')
+        .map(|line| {
+            let numbers = re.captures(line).ok_or_else(|| {
+                let message = "expected a sensor and its closest beacon";
+                ParseError::new(Day15::DAY, input, line, message)
+            })?;
+            let number = |i| {
+                let at = numbers.get(i).unwrap().as_str();
+                match parse_number::<isize>(Day15::DAY, input, at)? {
+                    n if (-MAX_COORDINATE..=MAX_COORDINATE).contains(&n) => Ok(n),
+                    _ => {
+                        let message = format!("expected a coordinate within {MAX_COORDINATE} of 0");
+                        Err(ParseError::new(Day15::DAY, input, at, message))
+                    }
+                }
+            };
+            let sensor = Point::new(number(1)?, number(2)?);
+            let beacon = Point::new(number(3)?, number(4)?);
+            Ok(Sensor::new(sensor, beacon))
+        })
+        .collect()
+}
+
+pub fn part_1(sensors: &[Sensor], y: isize) -> usize {
+    let mut coverage = Coverage::new();
+    for x_range in sensors.iter().flat_map(|sensor| sensor.x_range(y)) {
+        coverage.add_range(x_range);
+    }
+
+    let beacon_locations_in_row: HashSet<isize> = sensors
+        .iter()
+        .filter(|sensor| sensor.closest_beacon.y == y)
+        .map(|sensor| sensor.closest_beacon.x)
+        .collect();
+
+    coverage.len() - beacon_locations_in_row.len()
+}
+
+pub fn part_2(sensors: &[Sensor], search_space: &XRange) -> Option<isize> {
+    for y in search_space.min..=search_space.max {
+        let mut coverage = Coverage::new();
+        for x_range in sensors
+            .iter()
+            .flat_map(|sensor| sensor.x_range(y)?.constrained(search_space))
+        {
+            coverage.add_range(x_range);
+        }
+
+        if coverage.len() < search_space.len() {
+            // the first x in the row that no sensor rules out
+            let mut ranges: Vec<_> = coverage.ranges.iter().collect();
+            ranges.sort();
+            let mut x = search_space.min;
+            for range in ranges {
+                if range.min > x {
+                    break;
+                }
+                x = x.max(range.max + 1);
+            }
+            return Some(x * 4_000_000 + y);
+        }
+    }
+    None
+}
+
+// The example uses row 10 and a 0..=20 search space while the real puzzle
+// inputs use row 2,000,000 and 0..=4,000,000. The example's sensors all sit
+// within a few dozen units of the origin, so we pick based on their locations.
+pub fn scan_parameters(sensors: &[Sensor]) -> (isize, XRange) {
+    let example = sensors
+        .iter()
+        .all(|sensor| sensor.location.x.abs() < 1000 && sensor.location.y.abs() < 1000);
+
+    if example {
+        (10, XRange::new(0, 20))
+    } else {
+        (2_000_000, XRange::new(0, 4_000_000))
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: usize = 15;
+
+    type Input<'a> = Vec<Sensor>;
+    type Part1 = usize;
+    type Part2 = Maybe<isize>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part_1(sensors: &Vec<Sensor>) -> usize {
+        let (y, _) = scan_parameters(sensors);
+        part_1(sensors, y)
+    }
+
+    fn part_2(sensors: &Vec<Sensor>) -> Maybe<isize> {
+        let (_, search_space) = scan_parameters(sensors);
+        part_2(sensors, &search_space).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use std::fs;
 
     #[test]
     fn test_point_manhattan_distance() {
@@ -208,4 +324,73 @@ mod tests {
 
         assert_eq!(XRange::new(21, 25).constrained(&search_space), None);
     }
+
+    #[test]
+    fn test_parse_input() {
+        let input = fs::read_to_string("test-input.txt").unwrap();
+        let sensors = parse_input(&input).unwrap();
+
+        assert_eq!(sensors.len(), 14);
+        assert_eq!(
+            sensors[0],
+            Sensor::new(Point::new(2, 18), Point::new(-2, 15))
+        );
+        assert_eq!(
+            sensors[13],
+            Sensor::new(Point::new(20, 1), Point::new(15, 3))
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_input() {
+        let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9\n";
+        let error = parse_input(input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let input = "Sensor at x=2, y=99999999999999999999: closest beacon is at x=-2, y=15";
+        let error = parse_input(input).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 18));
+
+        let input = "Sensor at x=2, y=18: closest beacon is at x=-9223372036854775808, y=15";
+        let error = parse_input(input).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 45));
+    }
+
+    #[test]
+    fn test_scan_parameters() {
+        let input = fs::read_to_string("test-input.txt").unwrap();
+        let sensors = parse_input(&input).unwrap();
+        assert_eq!(scan_parameters(&sensors), (10, XRange::new(0, 20)));
+    }
+
+    #[test]
+    fn test_part_1() {
+        let input = fs::read_to_string("test-input.txt").unwrap();
+        let sensors = parse_input(&input).unwrap();
+        assert_eq!(part_1(&sensors, 10), 26);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let input = fs::read_to_string("test-input.txt").unwrap();
+        let sensors = &parse_input(&input).unwrap()[..];
+        assert_eq!(part_2(sensors, &XRange::new(0, 20)), Some(56_000_011));
+    }
+
+    proptest! {
+        #[test]
+        fn coverage_counts_every_x_once(ranges in vec((-50..50isize, 0..20isize), 0..20)) {
+            let mut coverage = Coverage::new();
+            for &(min, width) in &ranges {
+                coverage.add_range(XRange::new(min, min + width));
+            }
+
+            let covered: HashSet<isize> = ranges
+                .iter()
+                .flat_map(|&(min, width)| min..=min + width)
+                .collect();
+            prop_assert_eq!(coverage.len(), covered.len());
+            prop_assert_eq!(coverage.is_empty(), covered.is_empty());
+        }
+    }
 }
@@ -0,0 +1,76 @@
+// Generates inputs in the puzzle's format: one valve per line with its flow
+// rate and the valves its tunnels lead to, like "Valve BB has flow rate=13;
+// tunnels lead to valves CC, AA". The tunnels connect every valve, go both
+// ways, and about a quarter of the valves have a flow rate, like the puzzle's
+// input. `size` is the number of valves.
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = size.clamp(2, 26 * 26);
+
+    // AA and then distinct random ids
+    let mut ids = vec!["AA".to_string()];
+    while ids.len() < count {
+        let id: String = (0..2)
+            .map(|_| (b'A' + rng.below(26) as u8) as char)
+            .collect();
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+
+    // a random tree to connect every valve, with a few shortcuts
+    let mut tunnels = vec![vec![]; count];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for i in 1..count {
+        connect(i, rng.below(i));
+    }
+    for _ in 0..count / 4 {
+        connect(rng.below(count), rng.below(count));
+    }
+
+    let mut order: Vec<usize> = (0..count).collect();
+    rng.shuffle(&mut order);
+    order
+        .into_iter()
+        .map(|i| {
+            let flow_rate = match i != 0 && rng.chance(1, 4) {
+                true => rng.range(1..=25),
+                false => 0,
+            };
+            let next: Vec<&str> = tunnels[i].iter().map(|&j| ids[j].as_str()).collect();
+            let tunnels = match next.len() {
+                1 => "tunnel leads to valve",
+                _ => "tunnels lead to valves",
+            };
+            format!(
+                "Valve {} has flow rate={flow_rate}; {tunnels} {}\n",
+                ids[i],
+                next.join(", ")
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day16;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 12);
+            let volcano = Day16::parse(&input).unwrap();
+            assert_eq!(volcano.valves.len(), 12);
+            Day16::part_1(&volcano);
+            Day16::part_2(&volcano);
+        }
+    }
+}
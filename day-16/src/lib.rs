@@ -1,3 +1,49 @@
+pub mod generator;
 pub mod moves;
 pub mod state;
 pub mod volcano;
+
+use aoc_common::{Result, Solution};
+use volcano::{Strategy, Volcano};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: usize = 16;
+
+    type Input<'a> = Volcano<'a>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Volcano::new(input)
+    }
+
+    fn part_1(volcano: &Volcano) -> usize {
+        volcano.find_best_pressure(Strategy::Alone)
+    }
+
+    fn part_2(volcano: &Volcano) -> usize {
+        volcano.find_best_pressure(Strategy::WithElephant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_part_1() {
+        let input = fs::read_to_string("test-input.txt").unwrap();
+        let volcano = Day16::parse(&input).unwrap();
+        assert_eq!(Day16::part_1(&volcano), 1651);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let input = fs::read_to_string("test-input.txt").unwrap();
+        let volcano = Day16::parse(&input).unwrap();
+        assert_eq!(Day16::part_2(&volcano), 1707);
+    }
+}
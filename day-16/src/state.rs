@@ -30,14 +30,14 @@ impl<'a> GlobalState<'a> {
     pub fn apply_human_move(&mut self, m: &Move<'a>) {
         if m.action != Action::Wait {
             self.human_state = WorkerState::new(m.valve_id, m.minutes_elapsed);
-            self.apply_move(&m);
+            self.apply_move(m);
         }
     }
 
     pub fn apply_elephant_move(&mut self, m: &Move<'a>) {
         if m.action != Action::Wait {
             self.elephant_state = Some(WorkerState::new(m.valve_id, m.minutes_elapsed));
-            self.apply_move(&m);
+            self.apply_move(m);
         }
     }
 
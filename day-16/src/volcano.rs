@@ -1,5 +1,7 @@
 use crate::moves::{Action, Move};
 use crate::state::{GlobalState, WorkerState};
+use crate::Day16;
+use aoc_common::{parse_number, ParseError, Result, Solution};
 use itertools::iproduct;
 use regex::Regex;
 use std::collections::{HashMap, HashSet, VecDeque};
@@ -15,22 +17,26 @@ pub enum Strategy {
     WithElephant,
 }
 
+#[derive(Debug)]
 pub struct Volcano<'a> {
     pub valves: HashMap<&'a str, Valve<'a>>,
 }
 
 impl<'a> Volcano<'a> {
-    pub fn new(input: &'a str) -> Self {
+    pub fn new(input: &'a str) -> Result<Self> {
         let pattern = r"Valve ([A-Z]{2}) has flow rate=(\d+); tunnels? leads? to valves? (.*)";
-        let re = Regex::new(&pattern).unwrap();
+        let re = Regex::new(pattern).unwrap();
+        let error = |at, message: &str| ParseError::new(Day16::DAY, input, at, message);
 
-        let valves = input
+        let valves: HashMap<_, _> = input
             .trim()
             .split('\n')
             .map(|line| {
-                let captures = re.captures(&line).unwrap();
+                let captures = re
+                    .captures(line)
+                    .ok_or_else(|| error(line, "expected a valve and its tunnels"))?;
                 let id = captures.get(1).unwrap().as_str();
-                let flow_rate = captures.get(2).unwrap().as_str().parse().unwrap();
+                let flow_rate = parse_number(Day16::DAY, input, captures.get(2).unwrap().as_str())?;
                 let next_valves = captures
                     .get(3)
                     .unwrap()
@@ -39,11 +45,26 @@ impl<'a> Volcano<'a> {
                     .split(", ")
                     .collect();
 
-                (id, Valve::new(id, flow_rate, next_valves))
+                Ok((id, Valve::new(id, flow_rate, next_valves)))
             })
-            .collect();
+            .collect::<Result<_>>()?;
 
-        Self { valves }
+        // every tunnel has to lead somewhere, and we always start at AA
+        for valve in valves.values() {
+            if let Some(id) = valve
+                .next_valves
+                .iter()
+                .find(|id| !valves.contains_key(*id))
+            {
+                return Err(error(id, "tunnel leads to a valve that doesn't exist"));
+            }
+        }
+        if !valves.contains_key("AA") {
+            let end = &input[input.len()..];
+            return Err(error(end, "expected a valve with the id AA"));
+        }
+
+        Ok(Self { valves })
     }
 
     pub fn find_best_pressure(&self, strategy: Strategy) -> usize {
@@ -88,14 +109,15 @@ impl<'a> Volcano<'a> {
             }
         }
 
-        best_total_pressures.into_values().max().unwrap()
+        // no states at all means there were no valves worth opening
+        best_total_pressures.into_values().max().unwrap_or(0)
     }
 
     fn next_global_states(&self, global_state: &GlobalState<'a>) -> Vec<GlobalState<'a>> {
-        let next_human_moves = self.next_moves(&global_state.human_state, &global_state);
+        let next_human_moves = self.next_moves(&global_state.human_state, global_state);
         let next_elephant_moves = match &global_state.elephant_state {
-            Some(elephant_state) => self.next_moves(&elephant_state, &global_state),
-            None => vec![Move::new(&"AA", vec![], Wait, 0, 0)],
+            Some(elephant_state) => self.next_moves(elephant_state, global_state),
+            None => vec![Move::new("AA", vec![], Wait, 0, 0)],
         };
 
         if next_human_moves.is_empty() || next_elephant_moves.is_empty() {
@@ -105,12 +127,12 @@ impl<'a> Volcano<'a> {
         let mut next_global_states = vec![];
 
         for (human_move, elephant_move) in iproduct!(&next_human_moves, &next_elephant_moves) {
-            if human_move.ineffective_combination_with(&elephant_move) {
+            if human_move.ineffective_combination_with(elephant_move) {
                 continue;
             }
             let mut next_global_state = (*global_state).clone();
-            next_global_state.apply_human_move(&human_move);
-            next_global_state.apply_elephant_move(&elephant_move);
+            next_global_state.apply_human_move(human_move);
+            next_global_state.apply_elephant_move(elephant_move);
             next_global_states.push(next_global_state);
         }
 
@@ -133,7 +155,7 @@ impl<'a> Volcano<'a> {
 
         for id in &current_valve.next_valves {
             valves_to_check.push_back((
-                &id,
+                id,
                 worker_state.minutes_elapsed + 1,
                 vec![current_valve.id],
             ));
@@ -151,7 +173,7 @@ impl<'a> Volcano<'a> {
                     {
                         let mut valves_in_path = valves_in_path.clone();
                         valves_in_path.push(id);
-                        valves_to_check.push_back((&id, minutes + 1, valves_in_path));
+                        valves_to_check.push_back((id, minutes + 1, valves_in_path));
                     }
                 }
             } else {
@@ -209,7 +231,7 @@ mod tests {
     #[test]
     fn test_new_volcano() {
         let input = fs::read_to_string("test-input.txt").unwrap();
-        let volcano = Volcano::new(&input);
+        let volcano = Volcano::new(&input).unwrap();
         assert_eq!(volcano.valves.len(), 10);
         assert_eq!(
             volcano.valves.get("AA"),
@@ -220,4 +242,17 @@ mod tests {
             Some(&Valve::new("JJ", 21, vec!["II"]))
         );
     }
+
+    #[test]
+    fn test_new_invalid_volcano() {
+        let input = "Valve AA has flow rate=0; tunnels lead to valves BB, CC\n\
+                     Valve BB has flow rate=13; tunnel leads to valve AA\n";
+        let error = Volcano::new(input).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 54));
+        assert_eq!(error.snippet, "CC");
+
+        let input = "Valve AA has flow rate=0; tunnels lead to valves BB\nValve BB rate=13\n";
+        let error = Volcano::new(input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
 }
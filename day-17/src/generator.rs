@@ -0,0 +1,33 @@
+// Generates inputs in the puzzle's format: a pattern of jets pushing left (<)
+// and right (>), in short runs like the puzzle's input. `size` is the length
+// of the pattern.
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let len = size.max(1);
+    let mut pattern = String::with_capacity(len + 1);
+    while pattern.len() < len {
+        let jet = *rng.choose(&['<', '>']);
+        let run = rng.range(1..=4).min(len - pattern.len());
+        pattern.extend(std::iter::repeat_n(jet, run));
+    }
+    pattern + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day17;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..3 {
+            let input = generate(&mut Rng::new(seed), 100);
+            let gusts = Day17::parse(&input).unwrap();
+            assert_eq!(gusts.len(), 100);
+            Day17::part_1(&gusts);
+            Day17::part_2(&gusts);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+// Generates inputs in the puzzle's format: one unit cube per line, like
+// "2,2,2". The cubes fill about half of a box, so the droplet has pockets of
+// air inside it. `size` is the number of cubes.
+use aoc_common::Rng;
+use std::collections::HashSet;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = size.max(1);
+    let side = ((2 * count) as f64).cbrt().ceil() as usize + 1;
+
+    let mut cubes = HashSet::new();
+    let mut output = String::new();
+    while cubes.len() < count {
+        let cube = (rng.below(side), rng.below(side), rng.below(side));
+        if cubes.insert(cube) {
+            output += &format!("{},{},{}\n", cube.0, cube.1, cube.2);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day18;
+    use aoc_common::Solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 200);
+            let cubes = Day18::parse(&input).unwrap();
+            assert_eq!(cubes.len(), 200);
+            assert!(Day18::part_2(&cubes) <= Day18::part_1(&cubes));
+        }
+    }
+}
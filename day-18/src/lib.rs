@@ -1,17 +1,20 @@
-use std::collections::HashSet;
+pub mod generator;
+
+use aoc_common::{parse_number, ParseError, Point3, Result, Solution};
+use std::collections::{HashSet, VecDeque};
 use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
+// the cube from corner x,y,z to corner x+1,y+1,z+1
 #[derive(Clone, Debug)]
 pub struct UnitCube {
-    pub x: isize, // x..x+1
-    pub y: isize, // y..y+1
-    pub z: isize, // z..z+1
+    pub corner: Point3,
     pub faces: [[isize; 6]; 6],
 }
 
 impl PartialEq for UnitCube {
     fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y && self.z == other.z
+        self.corner == other.corner
     }
 }
 
@@ -19,92 +22,236 @@ impl Eq for UnitCube {}
 
 impl Hash for UnitCube {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.x.hash(state);
-        self.y.hash(state);
-        self.z.hash(state);
+        self.corner.hash(state);
     }
 }
 
 impl UnitCube {
     pub fn new(x: isize, y: isize, z: isize) -> Self {
-        let faces = Self::build_faces(x, y, z);
-        Self { x, y, z, faces }
-    }
-
-    pub fn coords(&self) -> (isize, isize, isize) {
-        (self.x, self.y, self.z)
+        Self::at(Point3::new(x, y, z))
     }
 
-    pub fn from_str(s: &str) -> Self {
-        let mut coords = s.split(',');
-        let mut next_coord = || coords.next().unwrap().parse().unwrap();
-        Self::new(next_coord(), next_coord(), next_coord())
+    pub fn at(corner: Point3) -> Self {
+        let faces = Self::build_faces(corner);
+        Self { corner, faces }
     }
 
     // each face is:  [xa, ya, za, xb, yb, zb]
     // bottom left --> ----------  ---------- <-- top right
-    pub fn build_faces(x: isize, y: isize, z: isize) -> [[isize; 6]; 6] {
-        let (x0, y0, z0, x1, y1, z1) = (x, y, z, x + 1, y + 1, z + 1);
+    // in the same order as the neighbouring cubes from Point3::neighbours
+    pub fn build_faces(corner: Point3) -> [[isize; 6]; 6] {
+        let Point3 {
+            x: x0,
+            y: y0,
+            z: z0,
+        } = corner;
+        let (x1, y1, z1) = (x0 + 1, y0 + 1, z0 + 1);
         [
-            [x0, y0, z1, x1, y1, z1], // back
-            [x0, y0, z0, x1, y1, z0], // front
-            [x0, y1, z0, x1, y1, z1], // top
-            [x0, y0, z0, x1, y0, z1], // bottom
             [x1, y0, z0, x1, y1, z1], // right
             [x0, y0, z0, x0, y1, z1], // left
+            [x0, y1, z0, x1, y1, z1], // top
+            [x0, y0, z0, x1, y0, z1], // bottom
+            [x0, y0, z1, x1, y1, z1], // back
+            [x0, y0, z0, x1, y1, z0], // front
         ]
     }
+}
 
-    // adjacent cubes within the given bounding cube in the same order as faces
-    pub fn adjacent_cubes_within(&self, bounding_cube: &BoundingCube) -> [Option<UnitCube>; 6] {
-        [
-            (self.z + 1 <= bounding_cube.max_z).then_some(Self::new(self.x, self.y, self.z + 1)),
-            (self.z - 1 >= bounding_cube.min_z).then_some(Self::new(self.x, self.y, self.z - 1)),
-            (self.y + 1 <= bounding_cube.max_y).then_some(Self::new(self.x, self.y + 1, self.z)),
-            (self.y - 1 >= bounding_cube.min_y).then_some(Self::new(self.x, self.y - 1, self.z)),
-            (self.x + 1 <= bounding_cube.max_x).then_some(Self::new(self.x + 1, self.y, self.z)),
-            (self.x - 1 >= bounding_cube.min_x).then_some(Self::new(self.x - 1, self.y, self.z)),
-        ]
+// far bigger than the puzzle's droplets, while leaving room for the faces and
+// bounding cube that reach one past each cube
+const MAX_COORDINATE: isize = 1 << 20;
+
+impl FromStr for UnitCube {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split(',').collect::<Vec<_>>()[..] {
+            [x, y, z] => {
+                let coord = |at| match parse_number(Day18::DAY, s, at)? {
+                    n if (-MAX_COORDINATE..=MAX_COORDINATE).contains(&n) => Ok(n),
+                    _ => {
+                        let message = format!("expected a coordinate within {MAX_COORDINATE} of 0");
+                        Err(ParseError::new(Day18::DAY, s, at, message))
+                    }
+                };
+                Ok(Self::new(coord(x)?, coord(y)?, coord(z)?))
+            }
+            _ => {
+                let message = "expected a cube written as x,y,z";
+                Err(ParseError::new(Day18::DAY, s, s, message))
+            }
+        }
     }
 }
 
+// the cubes from min to max, inclusive
 #[derive(Debug)]
 pub struct BoundingCube {
-    pub min_x: isize,
-    pub min_y: isize,
-    pub min_z: isize,
-    pub max_x: isize,
-    pub max_y: isize,
-    pub max_z: isize,
+    pub min: Point3,
+    pub max: Point3,
 }
 
 impl BoundingCube {
+    // the cube along with a layer of air all around it
     pub fn new(cube: &UnitCube) -> Self {
+        let one = Point3::new(1, 1, 1);
         Self {
-            min_x: cube.x - 1,
-            min_y: cube.y - 1,
-            min_z: cube.z - 1,
-            max_x: cube.x + 1,
-            max_y: cube.y + 1,
-            max_z: cube.z + 1,
+            min: cube.corner - one,
+            max: cube.corner + one,
         }
     }
 
     pub fn from_cubes(cubes: &HashSet<UnitCube>) -> Self {
-        assert!(cubes.len() > 0);
-        let mut bounding_cube = Self::new(&cubes.iter().next().unwrap());
+        assert!(!cubes.is_empty());
+        let mut bounding_cube = Self::new(cubes.iter().next().unwrap());
         for cube in cubes.iter() {
-            bounding_cube.include(&cube);
+            bounding_cube.include(cube);
         }
         bounding_cube
     }
 
     pub fn include(&mut self, cube: &UnitCube) {
-        self.min_x = self.min_x.min(cube.x - 1);
-        self.min_y = self.min_y.min(cube.y - 1);
-        self.min_z = self.min_z.min(cube.z - 1);
-        self.max_x = self.max_x.max(cube.x + 1);
-        self.max_y = self.max_y.max(cube.y + 1);
-        self.max_z = self.max_z.max(cube.z + 1);
+        let Self { min, max } = Self::new(cube);
+        self.min = Point3::new(
+            self.min.x.min(min.x),
+            self.min.y.min(min.y),
+            self.min.z.min(min.z),
+        );
+        self.max = Point3::new(
+            self.max.x.max(max.x),
+            self.max.y.max(max.y),
+            self.max.z.max(max.z),
+        );
+    }
+
+    pub fn contains(&self, point: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+}
+
+pub type Face = [isize; 6];
+
+pub fn parse_cubes(input: &str) -> Result<HashSet<UnitCube>> {
+    input
+        .trim()
+        .split('\n')
+        .map(|line| line.parse().map_err(|e: ParseError| e.within(input, line)))
+        .collect()
+}
+
+// faces that are not shared between two cubes
+pub fn exposed_faces(cubes: &HashSet<UnitCube>) -> HashSet<Face> {
+    let mut exposed_faces = HashSet::new();
+
+    for cube in cubes.iter() {
+        for face in cube.faces {
+            if !exposed_faces.insert(face) {
+                exposed_faces.remove(&face);
+            }
+        }
+    }
+
+    exposed_faces
+}
+
+// exposed faces that can be reached by flooding the bounding cube from the outside
+pub fn exterior_faces(cubes: &HashSet<UnitCube>, exposed_faces: &HashSet<Face>) -> HashSet<Face> {
+    let bounding_cube = BoundingCube::from_cubes(cubes);
+    let mut exterior_faces = HashSet::new();
+    let mut queue = VecDeque::new();
+    let mut enqueued = HashSet::new();
+
+    queue.push_back(UnitCube::at(bounding_cube.min));
+
+    while let Some(cube) = queue.pop_front() {
+        for (i, adjacent) in cube.corner.neighbours().into_iter().enumerate() {
+            if !bounding_cube.contains(adjacent) {
+                continue;
+            }
+            if exposed_faces.contains(&cube.faces[i]) {
+                exterior_faces.insert(cube.faces[i]);
+            } else if enqueued.insert(adjacent) {
+                queue.push_back(UnitCube::at(adjacent));
+            }
+        }
+    }
+
+    exterior_faces
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: usize = 18;
+
+    type Input<'a> = HashSet<UnitCube>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse_cubes(input)
+    }
+
+    fn part_1(cubes: &HashSet<UnitCube>) -> usize {
+        exposed_faces(cubes).len()
+    }
+
+    fn part_2(cubes: &HashSet<UnitCube>) -> usize {
+        exterior_faces(cubes, &exposed_faces(cubes)).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::collection::hash_set;
+    use proptest::prelude::*;
+    use std::fs;
+
+    #[test]
+    fn test_exposed_and_exterior_faces() {
+        let input = fs::read_to_string("test-input.txt").expect("failed to read input file");
+        let cubes = parse_cubes(&input).unwrap();
+        let exposed_faces = exposed_faces(&cubes);
+
+        assert_eq!(exposed_faces.len(), 64);
+        assert_eq!(exterior_faces(&cubes, &exposed_faces).len(), 58);
+    }
+
+    #[test]
+    fn test_parse_cubes() {
+        assert_eq!(
+            parse_cubes("1,2,3\n"),
+            Ok(HashSet::from([UnitCube::new(1, 2, 3)]))
+        );
+
+        let error = parse_cubes("1,2,3\n2,2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = parse_cubes("1,2,3\n2,-,2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+
+        let error = parse_cubes("1,2,3\n2,9223372036854775807,2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    proptest! {
+        #[test]
+        fn faces_match_neighbouring_cubes(coords in hash_set((0..5isize, 0..5isize, 0..5isize), 1..60)) {
+            let cubes: HashSet<UnitCube> =
+                coords.iter().map(|&(x, y, z)| UnitCube::new(x, y, z)).collect();
+            let neighbours = [(1, 0, 0), (-1, 0, 0), (0, 1, 0), (0, -1, 0), (0, 0, 1), (0, 0, -1)];
+            let open_sides = coords
+                .iter()
+                .flat_map(|&(x, y, z)| neighbours.map(|(dx, dy, dz)| (x + dx, y + dy, z + dz)))
+                .filter(|neighbour| !coords.contains(neighbour))
+                .count();
+
+            let exposed = exposed_faces(&cubes);
+            prop_assert_eq!(exposed.len(), open_sides);
+            prop_assert!(exterior_faces(&cubes, &exposed).len() <= exposed.len());
+        }
     }
 }
@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day_04::Day04;
+use libfuzzer_sys::fuzz_target;
+
+// parsing must never panic, whatever it's given
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = Day04::parse(input);
+    }
+});
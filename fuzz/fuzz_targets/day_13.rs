@@ -0,0 +1,13 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day_13::{Day13, Element};
+use libfuzzer_sys::fuzz_target;
+
+// parsing must never panic, whatever it's given
+fuzz_target!(|data: &[u8]| {
+    let _ = Element::from_bytes(data);
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = Day13::parse(input);
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day_16::Day16;
+use libfuzzer_sys::fuzz_target;
+
+// parsing must never panic, whatever it's given
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = Day16::parse(input);
+    }
+});